@@ -1,13 +1,34 @@
 use cosmwasm_std::{
-    entry_point, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, BankMsg, Binary,
-    to_json_binary, CosmosMsg, WasmMsg, StdError,
+    entry_point, Addr, Api, Storage, Timestamp, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128, BankMsg,
+    Binary, to_json_binary, Coin, CosmosMsg, Decimal, Empty, StdError, WasmMsg,
 };
-use cw20::Cw20ExecuteMsg;
-use serde_json;
+use cw_storage_plus::{Bound, PrefixBound};
+use cw2::{set_contract_version, CONTRACT};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse, TokenInfoResponse};
+use cw_utils::Expiration;
+use semver::Version;
+use crate::curve::{cap_ratio, MintCurve, MintSegment};
+use crate::error::ContractError;
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, QueryMsg, Cw20AddressResponse, ConfigResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Cw20AddressResponse, ConfigResponse, AdminResponse,
+    SimulateMintResponse, ReverseSimulateMintResponse, MintHistoryResponse, AddressTotalsResponse,
+    AllMintsResponse, MintAllowanceResponse, OrderBy, RateLimitStatusResponse, TokenStatusIssue,
+    TokenStatusResponse, BurnerInfo, TopBurnersResponse, BurnerRankResponse,
 };
-use crate::state::{Config, CONFIG};
+use crate::state::{
+    address_totals, AddressTotals, BurnTarget, Config, MintEvent, MintRecord, PendingAdmin, RateLimit,
+    RateLimitWindow, BURN_DENOMS, CONFIG, LEGACY_BURN_ADDRESS, LEGACY_BURN_DENOM, LEGACY_CONFIG, MINT_EVENTS, MINT_EVENT_COUNT, MINT_HISTORY,
+    PENDING_ADMIN, RATE_LIMIT_WINDOW,
+};
+
+const CONTRACT_NAME: &str = "crates.io:cw20-mint-and-burn";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Deepest rank `BurnerRank` resolves. Finding a rank reads one index entry per burner
+/// ranked above, so the scan stops here to keep the query's gas bounded.
+const MAX_RANK: u64 = 1_000;
 
 #[entry_point]
 pub fn instantiate(
@@ -15,26 +36,95 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let addr = deps.api.addr_validate(&msg.cw20_address)?;
+    validate_denom(&msg.burn_denom)?;
+    let mint_curve = msg.mint_curve.unwrap_or_default();
+    mint_curve.validate()?;
+    let total_tokens_minted = msg.initial_tokens_minted.unwrap_or(Uint128::zero());
+    if msg.max_total_minted.is_some_and(|max| max < total_tokens_minted) {
+        return Err(ContractError::InvalidSupplyCap { total_tokens_minted });
+    }
     let config = Config {
         cw20_address: addr.clone(),
-        admin: info.sender.clone(),
-        total_uluna_burned: msg.initial_uluna_burned.unwrap_or(Uint128::zero()),
-        total_tokens_minted: msg.initial_tokens_minted.unwrap_or(Uint128::zero()),
+        admin: Some(info.sender.clone()),
+        burn_denom: msg.burn_denom,
+        burn_target: validate_burn_target(deps.api, msg.burn_target)?,
+        total_burned: msg.initial_burned.unwrap_or(Uint128::zero()),
+        total_tokens_minted,
+        lifetime_tokens_minted: total_tokens_minted,
         burn_threshold: Uint128::new(1_000_000_000_000), // Default 1T
-        max_mint_ratio: Uint128::zero(), // Default 0 (no limit)
+        max_mint_ratio: Decimal::zero(), // Default 0 (no limit)
+        min_burn_amount: Uint128::zero(),
+        refund_remainder: false,
+        max_burn_per_tx: Uint128::zero(),
+        max_mint_per_address: Uint128::zero(),
+        rate_limit: None,
+        max_total_minted: msg.max_total_minted,
+        mint_curve,
         paused: false, // Default to not paused
     };
     CONFIG.save(deps.storage, &config)?;
+    BURN_DENOMS.save(deps.storage, &config.burn_denom, &Empty {})?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("admin", info.sender.to_string())
         .add_attribute("cw20_address", msg.cw20_address)
-        .add_attribute("initial_uluna_burned", config.total_uluna_burned.to_string())
+        .add_attribute("initial_burned", config.total_burned.to_string())
         .add_attribute("initial_tokens_minted", config.total_tokens_minted.to_string())
         .add_attribute("burn_threshold", config.burn_threshold.to_string())
-        .add_attribute("max_mint_ratio", config.max_mint_ratio.to_string()))
+        .add_attribute("max_mint_ratio", config.max_mint_ratio.to_string())
+        .add_attribute("burn_denom", config.burn_denom)
+        .add_attribute("burn_target", config.burn_target.to_string())
+        .add_attribute("max_total_minted", config.max_total_minted.map_or_else(|| "none".to_string(), |max| max.to_string())))
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let current = parse_version(CONTRACT_VERSION)?;
+    let previous_version = match CONTRACT.may_load(deps.storage)? {
+        Some(stored) => {
+            if stored.contract != CONTRACT_NAME {
+                return Err(ContractError::InvalidContractName {
+                    expected: CONTRACT_NAME.to_string(),
+                    actual: stored.contract,
+                });
+            }
+            if parse_version(&stored.version)? > current {
+                return Err(ContractError::CannotMigrateDowngrade {
+                    stored: stored.version,
+                    current: CONTRACT_VERSION.to_string(),
+                });
+            }
+            stored.version
+        },
+        // Only the original 0.1.0 release was deployed without cw2 info. Its config
+        // predates the burn denom and burn target, so those are filled in from the
+        // migrate message or fall back to the values that release hardcoded.
+        None => {
+            let legacy = LEGACY_CONFIG.load(deps.storage)?;
+            let burn_denom = msg.burn_denom.unwrap_or_else(|| LEGACY_BURN_DENOM.to_string());
+            validate_denom(&burn_denom)?;
+            let burn_target = match msg.burn_target {
+                Some(target) => validate_burn_target(deps.api, target)?,
+                None => BurnTarget::Address {
+                    address: deps.api.addr_validate(LEGACY_BURN_ADDRESS)?,
+                },
+            };
+            CONFIG.save(deps.storage, &legacy.into_config(burn_denom, burn_target))?;
+            "0.1.0".to_string()
+        },
+    };
+
+    // Deployments from before burn denoms were tracked only know their current one
+    let config = CONFIG.load(deps.storage)?;
+    BURN_DENOMS.save(deps.storage, &config.burn_denom, &Empty {})?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("previous_version", previous_version)
+        .add_attribute("new_version", CONTRACT_VERSION))
 }
 
 #[entry_point]
@@ -43,123 +133,555 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetCw20Address { address } => try_set_address(deps, info, address),
-        ExecuteMsg::Mint {} => {
-            if let Some(uluna_amount) = info.funds.iter().find(|c| c.denom == "uluna").map(|c| c.amount) {
-                try_mint(deps, env, info, uluna_amount)
-            } else {
-                Err(StdError::generic_err("No uluna sent for minting"))
-            }
+        ExecuteMsg::SetCw20Address { address, tokens_minted, skip_minter_check } => {
+            try_set_address(deps, env, info, address, tokens_minted, skip_minter_check.unwrap_or(false))
+        },
+        ExecuteMsg::Mint { recipient, min_tokens_out, deadline } => {
+            let recipient = match recipient {
+                Some(recipient) => deps.api.addr_validate(&recipient)?,
+                None => info.sender.clone(),
+            };
+            try_mint(deps, env, info, MintDelivery::Transfer { recipient }, min_tokens_out, deadline)
+        },
+        ExecuteMsg::MintAndSend { contract, msg, min_tokens_out, deadline } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            try_mint(deps, env, info, MintDelivery::Send { contract, msg }, min_tokens_out, deadline)
         },
-        ExecuteMsg::UpdateMinter { new_minter } => try_update_minter(deps, info, new_minter),
+        ExecuteMsg::UpdateMinter { new_minter } => try_update_minter(deps, env, info, new_minter),
         ExecuteMsg::SetBurnThreshold { threshold } => try_set_burn_threshold(deps, info, threshold),
         ExecuteMsg::SetMaxMintRatio { max_ratio } => try_set_max_mint_ratio(deps, info, max_ratio),
+        ExecuteMsg::SetMinBurnAmount { amount } => try_set_min_burn_amount(deps, info, amount),
+        ExecuteMsg::SetMaxBurnPerTx { amount } => try_set_max_burn_per_tx(deps, info, amount),
+        ExecuteMsg::SetMaxMintPerAddress { amount } => try_set_max_mint_per_address(deps, info, amount),
+        ExecuteMsg::SetRateLimit { rate_limit } => try_set_rate_limit(deps, info, rate_limit),
+        ExecuteMsg::LowerMaxTotalMinted { max_total_minted } => {
+            try_lower_max_total_minted(deps, info, max_total_minted)
+        },
+        ExecuteMsg::SetRefundRemainder { refund_remainder } => {
+            try_set_refund_remainder(deps, info, refund_remainder)
+        },
         ExecuteMsg::SetPaused { paused } => try_set_paused(deps, info, paused),
+        ExecuteMsg::SetBurnDenom { denom } => try_set_burn_denom(deps, info, denom),
+        ExecuteMsg::SetBurnTarget { target } => try_set_burn_target(deps, info, target),
+        ExecuteMsg::SetMintCurve { curve } => try_set_mint_curve(deps, info, curve),
+        ExecuteMsg::ProposeNewAdmin { new_admin, expires } => {
+            try_propose_new_admin(deps, env, info, new_admin, expires)
+        },
+        ExecuteMsg::AcceptAdmin {} => try_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => try_cancel_admin_proposal(deps, info),
+        ExecuteMsg::RenounceAdmin {} => try_renounce_admin(deps, info),
+        ExecuteMsg::RecoverFunds { denom, amount, recipient } => {
+            try_recover_funds(deps, env, info, denom, amount, recipient)
+        },
     }
 }
 
-fn try_set_address(deps: DepsMut, info: MessageInfo, address: String) -> StdResult<Response> {
+/// Points the contract at a new CW20. Unless `skip_minter_check` is set, the token
+/// must name this contract as its minter. Switching tokens requires `tokens_minted`,
+/// the new token's count of tokens minted through this contract, which cannot
+/// exceed its supply; `total_burned` carries over so the curve keeps its place.
+/// The supply cap is charged against `lifetime_tokens_minted`, which is left alone.
+fn try_set_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    tokens_minted: Option<Uint128>,
+    skip_minter_check: bool,
+) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("Only admin can set CW20 address"));
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set CW20 address".to_string() });
     }
 
     let addr = deps.api.addr_validate(&address)?;
-    config.cw20_address = addr.clone();
-    CONFIG.save(deps.storage, &config)?;
-    Ok(Response::new().add_attribute("action", "set_cw20_address"))
-}
+    match (addr != config.cw20_address, tokens_minted) {
+        (true, None) => return Err(ContractError::TokensMintedRequired {}),
+        (false, Some(_)) => return Err(ContractError::TokensMintedUnchanged {}),
+        _ => {},
+    }
+    if !skip_minter_check {
+        let minter: Option<MinterResponse> = deps
+            .querier
+            .query_wasm_smart(&addr, &Cw20QueryMsg::Minter {})
+            .map_err(|_| ContractError::NotCw20 { address: addr.to_string() })?;
+        if minter.map(|m| m.minter) != Some(env.contract.address.to_string()) {
+            return Err(ContractError::NotCw20Minter { address: addr.to_string() });
+        }
+    }
+    if let Some(tokens_minted) = tokens_minted {
+        let token_info: TokenInfoResponse = deps
+            .querier
+            .query_wasm_smart(&addr, &Cw20QueryMsg::TokenInfo {})
+            .map_err(|_| ContractError::NotCw20 { address: addr.to_string() })?;
+        if tokens_minted > token_info.total_supply {
+            return Err(ContractError::TokensMintedAboveSupply {
+                tokens_minted,
+                total_supply: token_info.total_supply,
+            });
+        }
+    }
 
-fn try_mint(deps: DepsMut, _env: Env, info: MessageInfo, amount: Uint128) -> StdResult<Response> {
-    let mut config = CONFIG.load(deps.storage)?;
-    if config.paused {
-        return Err(StdError::generic_err("Minting is currently paused"));
+    let previous_address = std::mem::replace(&mut config.cw20_address, addr.clone());
+    if let Some(tokens_minted) = tokens_minted {
+        config.total_tokens_minted = tokens_minted;
     }
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_cw20_address")
+        .add_attribute("previous_cw20_address", previous_address.to_string())
+        .add_attribute("cw20_address", addr.to_string())
+        .add_attribute("total_tokens_minted", config.total_tokens_minted.to_string()))
+}
 
-    let burn_address = deps.api.addr_validate("terra1sk06e3dyexuq4shw77y3dsv480xv42mq73anxu")?;
+/// Where `try_mint` delivers the minted tokens.
+enum MintDelivery {
+    /// Mint straight to `recipient`
+    Transfer { recipient: Addr },
+    /// Mint to this contract, then `Send` the tokens on to `contract` with the `msg` hook
+    Send { contract: Addr, msg: Binary },
+}
 
-    let calculated_ratio = if config.total_uluna_burned < Uint128::new(5_000_000_000_000) {
-        Uint128::one()
-    } else {
-        ((config.total_uluna_burned - Uint128::new(5_000_000_000_000)) / config.burn_threshold) + Uint128::new(2)
-    };
+impl MintDelivery {
+    fn recipient(&self) -> &Addr {
+        match self {
+            MintDelivery::Transfer { recipient } => recipient,
+            MintDelivery::Send { contract, .. } => contract,
+        }
+    }
+}
 
-    let mint_ratio = if config.max_mint_ratio.is_zero() || calculated_ratio <= config.max_mint_ratio {
-        calculated_ratio
-    } else {
-        config.max_mint_ratio
-    };
+/// Amount of the burn denom attached to the message. Anything other than a single
+/// nonzero coin of the burn denom is rejected, so no other funds get stuck here.
+fn burn_funds(config: &Config, info: &MessageInfo) -> Result<Uint128, ContractError> {
+    match info.funds.as_slice() {
+        [] => Err(ContractError::NoFunds { denom: config.burn_denom.clone() }),
+        [coin] if coin.denom == config.burn_denom => {
+            if coin.amount.is_zero() {
+                return Err(ContractError::NoFunds { denom: config.burn_denom.clone() });
+            }
+            Ok(coin.amount)
+        },
+        [coin] => Err(ContractError::WrongDenom {
+            expected: config.burn_denom.clone(),
+            received: coin.denom.clone(),
+        }),
+        funds => Err(ContractError::MultipleCoins {
+            expected: config.burn_denom.clone(),
+            received: funds.iter().map(|c| c.denom.as_str()).collect::<Vec<_>>().join(","),
+        }),
+    }
+}
 
-    let mint_amount = amount
-        .checked_div(mint_ratio)
-        .map_err(|_| StdError::generic_err("Division by zero in mint amount calculation"))?;
+fn try_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delivery: MintDelivery,
+    min_tokens_out: Option<Uint128>,
+    deadline: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    if let Some(deadline) = deadline {
+        if deadline.is_expired(&env.block) {
+            return Err(ContractError::DeadlineExpired { deadline });
+        }
+    }
+    let mut config = CONFIG.load(deps.storage)?;
+    // Report the pause before complaining about the funds sent
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    let limits = MintLimits::load(deps.storage, &config, env.block.time, Some(&info.sender))?;
+    let sent = burn_funds(&config, &info)?;
+    let recipient = delivery.recipient().clone();
+    let MintCalculation {
+        burn_amount: amount,
+        refund_amount,
+        mint_amount,
+        mint_ratio,
+        segments,
+        ..
+    } = calculate_mint(&config, &limits, sent)?;
+    if let Some(min_tokens_out) = min_tokens_out {
+        if mint_amount < min_tokens_out {
+            return Err(ContractError::SlippageExceeded { minted: mint_amount, min_tokens_out });
+        }
+    }
+    let mut totals = address_totals().may_load(deps.storage, &info.sender)?.unwrap_or_default();
+    if let Some(mut window) = limits.window {
+        window.minted += mint_amount;
+        RATE_LIMIT_WINDOW.save(deps.storage, &window)?;
+    }
 
-    config.total_uluna_burned += amount;
+    config.total_burned += amount;
     config.total_tokens_minted += mint_amount;
+    config.lifetime_tokens_minted += mint_amount;
     CONFIG.save(deps.storage, &config)?;
 
-    let response = Response::new()
-        .add_message(BankMsg::Send {
-            to_address: burn_address.to_string(),
-            amount: vec![cosmwasm_std::Coin {
-                denom: "uluna".to_string(),
-                amount,
-            }],
-        })
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.cw20_address.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
-                recipient: info.sender.to_string(),
+    let effective_ratio = Decimal::from_ratio(amount, mint_amount);
+    let record = MintRecord {
+        id: totals.mint_count,
+        recipient: recipient.clone(),
+        height: env.block.height,
+        time: env.block.time,
+        burned: amount,
+        minted: mint_amount,
+        ratio: effective_ratio,
+    };
+    MINT_HISTORY.save(deps.storage, (&info.sender, record.id), &record)?;
+    totals.burned += amount;
+    totals.minted += mint_amount;
+    totals.mint_count += 1;
+    address_totals().save(deps.storage, &info.sender, &totals)?;
+
+    let event_id = MINT_EVENT_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    MINT_EVENTS.save(deps.storage, event_id, &MintEvent {
+        id: event_id,
+        sender: info.sender.clone(),
+        recipient: recipient.clone(),
+        burned: amount,
+        minted: mint_amount,
+        ratio: effective_ratio,
+        height: env.block.height,
+        time: env.block.time,
+    })?;
+    MINT_EVENT_COUNT.save(deps.storage, &(event_id + 1))?;
+
+    let burn_coins = vec![Coin {
+        denom: config.burn_denom.clone(),
+        amount,
+    }];
+    let burn_msg = match &config.burn_target {
+        BurnTarget::Address { address } => Some(BankMsg::Send {
+            to_address: address.to_string(),
+            amount: burn_coins,
+        }),
+        BurnTarget::HoldInContract {} => None,
+        BurnTarget::ModuleBurn {} => Some(BankMsg::Burn { amount: burn_coins }),
+    };
+    let refund_msg = (!refund_amount.is_zero()).then(|| BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![Coin {
+            denom: config.burn_denom.clone(),
+            amount: refund_amount,
+        }],
+    });
+
+    let mint_msgs = match delivery {
+        MintDelivery::Transfer { recipient } => vec![Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: mint_amount,
+        }],
+        MintDelivery::Send { contract, msg } => vec![
+            Cw20ExecuteMsg::Mint {
+                recipient: env.contract.address.to_string(),
                 amount: mint_amount,
-            })?,
-            funds: vec![],
-        }))
+            },
+            Cw20ExecuteMsg::Send {
+                contract: contract.to_string(),
+                amount: mint_amount,
+                msg,
+            },
+        ],
+    };
+    let mint_msgs = mint_msgs
+        .into_iter()
+        .map(|msg| -> StdResult<_> {
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.cw20_address.to_string(),
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
+            }))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut response = Response::new()
+        .add_messages(burn_msg)
+        .add_messages(refund_msg)
+        .add_messages(mint_msgs)
         .add_attribute("action", "mint_cw20")
-        .add_attribute("uluna_amount", amount.to_string())
+        .add_attribute("burn_amount", amount.to_string())
         .add_attribute("mint_amount", mint_amount.to_string())
         .add_attribute("mint_ratio", mint_ratio.to_string())
         .add_attribute("max_mint_ratio", config.max_mint_ratio.to_string())
-        .add_attribute("total_uluna_burned", config.total_uluna_burned.to_string())
-        .add_attribute("total_tokens_minted", config.total_tokens_minted.to_string());
+        .add_attribute("total_burned", config.total_burned.to_string())
+        .add_attribute("total_tokens_minted", config.total_tokens_minted.to_string())
+        .add_attribute("burn_target", config.burn_target.to_string())
+        .add_attribute("burner", info.sender.to_string())
+        .add_attribute("recipient", recipient.to_string())
+        .add_attribute("refunded", refund_amount.to_string())
+        .add_attribute("tiers", segments.len().to_string());
+    for (i, segment) in segments.iter().enumerate() {
+        response = response
+            .add_attribute(format!("tier_{}_burned", i), segment.burned.to_string())
+            .add_attribute(format!("tier_{}_ratio", i), segment.ratio.to_string())
+            .add_attribute(format!("tier_{}_minted", i), segment.minted.to_string());
+    }
 
     Ok(response)
 }
 
-fn try_update_minter(deps: DepsMut, info: MessageInfo, new_minter: String) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("Only admin can update minter"));
+/// Outcome of burning `amount` against the current config. `try_mint` and the
+/// `SimulateMint` query both go through `calculate_mint`, so they cannot drift.
+struct MintCalculation {
+    /// Part of the amount that is actually burned
+    burn_amount: Uint128,
+    /// Rounding remainder sent back when `refund_remainder` is on
+    refund_amount: Uint128,
+    mint_amount: Uint128,
+    /// Ratio before the mint
+    mint_ratio: Decimal,
+    /// Ratio the next mint would start at
+    ratio_after: Decimal,
+    segments: Vec<MintSegment>,
+}
+
+/// Limits on a mint that depend on state outside `Config`. Loaded once per call so
+/// `try_mint` and the simulations check them in `calculate_mint` alike.
+struct MintLimits {
+    /// Rate limit window in effect at the block time, when a rate limit is set
+    window: Option<RateLimitWindow>,
+    /// Tokens the burner can still mint, when it is known and limited
+    allowance: Option<Uint128>,
+}
+
+impl MintLimits {
+    fn load(storage: &dyn Storage, config: &Config, now: Timestamp, burner: Option<&Addr>) -> StdResult<Self> {
+        let window = match &config.rate_limit {
+            Some(rate_limit) => {
+                Some(RateLimitWindow::current(RATE_LIMIT_WINDOW.may_load(storage)?, rate_limit, now))
+            },
+            None => None,
+        };
+        let allowance = match burner {
+            Some(burner) => {
+                let totals = address_totals().may_load(storage, burner)?.unwrap_or_default();
+                remaining_allowance(config, &totals)
+            },
+            None => None,
+        };
+        Ok(MintLimits { window, allowance })
+    }
+
+    /// Tokens left in the current rate limit window and when it resets, or `None`
+    /// without a rate limit.
+    fn rate_limit_left(&self, config: &Config) -> Option<(Uint128, Timestamp)> {
+        let rate_limit = config.rate_limit.as_ref()?;
+        let window = self.window.as_ref()?;
+        Some((
+            rate_limit.max_minted.saturating_sub(window.minted),
+            window.start.plus_seconds(rate_limit.window_seconds),
+        ))
+    }
+}
+
+fn calculate_mint(config: &Config, limits: &MintLimits, amount: Uint128) -> Result<MintCalculation, ContractError> {
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    if amount < config.min_burn_amount {
+        return Err(ContractError::BurnBelowMinimum { amount, min_burn_amount: config.min_burn_amount });
+    }
+    if !config.max_burn_per_tx.is_zero() && amount > config.max_burn_per_tx {
+        return Err(ContractError::BurnAboveMaximum { amount, max_burn_per_tx: config.max_burn_per_tx });
+    }
+
+    let mint_ratio = config.mint_ratio()?;
+    let split = |amount: Uint128| {
+        config.mint_curve.split_mint(
+            config.total_burned,
+            amount,
+            config.burn_threshold,
+            config.max_mint_ratio,
+        )
+    };
+    let minted = |segments: &[MintSegment]| segments.iter().map(|s| s.minted).sum::<Uint128>();
+    let mut segments = split(amount)?;
+    let mut mint_amount = minted(&segments);
+    let mut burn_amount = amount;
+
+    // Past the supply cap, fill only the largest burn that stays within it
+    let mut capped = false;
+    if let Some(supply_left) = config.remaining_supply() {
+        if supply_left.is_zero() {
+            return Err(ContractError::SupplyCapReached {
+                max_total_minted: config.max_total_minted.unwrap_or_default(),
+            });
+        }
+        if mint_amount > supply_left {
+            let mut low = Uint128::zero();
+            while burn_amount - low > Uint128::one() {
+                let mid = low + (burn_amount - low) / Uint128::new(2);
+                if minted(&split(mid)?) <= supply_left {
+                    low = mid;
+                } else {
+                    burn_amount = mid;
+                }
+            }
+            burn_amount = low;
+            segments = split(burn_amount)?;
+            mint_amount = minted(&segments);
+            capped = true;
+        }
+    }
+    if mint_amount.is_zero() {
+        return Err(ContractError::ZeroMintAmount { amount, ratio: mint_ratio });
+    }
+    if let Some(remaining) = limits.allowance {
+        if mint_amount > remaining {
+            return Err(ContractError::AddressMintLimitExceeded { minted: mint_amount, remaining });
+        }
+    }
+    if let Some((remaining, resets_at)) = limits.rate_limit_left(config) {
+        if mint_amount > remaining {
+            return Err(ContractError::RateLimitExceeded { minted: mint_amount, remaining, resets_at });
+        }
+    }
+
+    // Burn only the smallest amount that still mints `mint_amount`, which exists
+    // because minted amounts never decrease as the burn grows. A partial fill
+    // always refunds what it does not burn.
+    if config.refund_remainder || capped {
+        let mut low = Uint128::zero();
+        while burn_amount - low > Uint128::one() {
+            let mid = low + (burn_amount - low) / Uint128::new(2);
+            let mid_segments = split(mid)?;
+            if minted(&mid_segments) >= mint_amount {
+                burn_amount = mid;
+                segments = mid_segments;
+            } else {
+                low = mid;
+            }
+        }
+    }
+
+    let burned_after = config.total_burned.checked_add(burn_amount).map_err(StdError::from)?;
+    let ratio_after = cap_ratio(
+        config.mint_curve.ratio(burned_after, config.burn_threshold)?,
+        config.max_mint_ratio,
+    );
+    Ok(MintCalculation {
+        burn_amount,
+        refund_amount: amount - burn_amount,
+        mint_amount,
+        mint_ratio,
+        ratio_after,
+        segments,
+    })
+}
+
+/// Tokens `totals`' burner can still mint under `max_mint_per_address`, or `None`
+/// when there is no limit.
+fn remaining_allowance(config: &Config, totals: &AddressTotals) -> Option<Uint128> {
+    if config.max_mint_per_address.is_zero() {
+        return None;
+    }
+    Some(config.max_mint_per_address.saturating_sub(totals.minted))
+}
+
+/// Smallest burn whose mint yields at least `desired_tokens`, found by searching over
+/// `calculate_mint` itself so tier splits and `max_mint_ratio` are accounted for
+/// exactly. Minted amounts never decrease as the burn grows, which makes the
+/// search valid.
+fn reverse_calculate_mint(
+    config: &Config,
+    limits: &MintLimits,
+    desired_tokens: Uint128,
+) -> Result<(Uint128, MintCalculation), ContractError> {
+    if desired_tokens.is_zero() {
+        return Err(ContractError::ZeroDesiredTokens {});
+    }
+    let left = [
+        config.remaining_supply(),
+        limits.allowance,
+        limits.rate_limit_left(config).map(|(left, _)| left),
+    ];
+    if left.iter().flatten().any(|left| *left < desired_tokens) {
+        return Err(ContractError::UnreachableMintAmount { desired: desired_tokens });
+    }
+
+    // Ok(None) when the burn falls short of the target
+    let try_amount = |amount: Uint128| match calculate_mint(config, limits, amount) {
+        Ok(calculation) if calculation.mint_amount >= desired_tokens => Ok(Some(calculation)),
+        Ok(_) | Err(ContractError::ZeroMintAmount { .. }) | Err(ContractError::BurnBelowMinimum { .. }) => {
+            Ok(None)
+        },
+        Err(ContractError::BurnAboveMaximum { .. })
+        | Err(ContractError::AddressMintLimitExceeded { .. })
+        | Err(ContractError::RateLimitExceeded { .. }) => {
+            Err(ContractError::UnreachableMintAmount { desired: desired_tokens })
+        },
+        Err(err) => Err(err),
+    };
+
+    let mut low = Uint128::zero();
+    let mut high = desired_tokens;
+    while let Ok(None) = try_amount(high) {
+        low = high;
+        high = high
+            .checked_mul(Uint128::new(2))
+            .map_err(|_| ContractError::UnreachableMintAmount { desired: desired_tokens })?;
     }
 
-    let update_minter_msg = serde_json::to_vec(&serde_json::json!({
-        "update_minter": {
-            "new_minter": new_minter.clone()
+    while high - low > Uint128::one() {
+        let mid = low + (high - low) / Uint128::new(2);
+        match try_amount(mid) {
+            Ok(None) => low = mid,
+            _ => high = mid,
         }
-    }))
-    .map_err(|e| StdError::generic_err(format!("Failed to serialize update_minter message: {}", e)))?;
+    }
+
+    match try_amount(high)? {
+        Some(calculation) => Ok((high, calculation)),
+        None => Err(ContractError::UnreachableMintAmount { desired: desired_tokens }),
+    }
+}
+
+/// Hands the CW20's minter role to `new_minter`, or removes it for good with `None`.
+/// Mints would fail from then on, so the contract pauses itself unless the role
+/// stays with this contract.
+fn try_update_minter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_minter: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "update minter".to_string() });
+    }
+
+    let new_minter = new_minter.map(|minter| deps.api.addr_validate(&minter)).transpose()?;
+    if new_minter.as_ref() != Some(&env.contract.address) {
+        config.paused = true;
+        CONFIG.save(deps.storage, &config)?;
+    }
 
     let response = Response::new()
         .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: config.cw20_address.to_string(),
-            msg: Binary(update_minter_msg),
+            msg: to_json_binary(&Cw20ExecuteMsg::UpdateMinter {
+                new_minter: new_minter.as_ref().map(Addr::to_string),
+            })?,
             funds: vec![],
         }))
         .add_attribute("action", "update_minter")
-        .add_attribute("new_minter", new_minter);
+        .add_attribute("new_minter", new_minter.map_or_else(|| "none".to_string(), String::from))
+        .add_attribute("paused", config.paused.to_string());
 
     Ok(response)
 }
 
-fn try_set_burn_threshold(deps: DepsMut, info: MessageInfo, threshold: Uint128) -> StdResult<Response> {
+fn try_set_burn_threshold(deps: DepsMut, info: MessageInfo, threshold: Uint128) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("Only admin can set burn threshold"));
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set burn threshold".to_string() });
     }
 
     if threshold.is_zero() {
-        return Err(StdError::generic_err("Burn threshold cannot be zero"));
+        return Err(ContractError::ZeroThreshold {});
     }
 
     config.burn_threshold = threshold;
@@ -169,10 +691,10 @@ fn try_set_burn_threshold(deps: DepsMut, info: MessageInfo, threshold: Uint128)
         .add_attribute("threshold", threshold.to_string()))
 }
 
-fn try_set_max_mint_ratio(deps: DepsMut, info: MessageInfo, max_ratio: Uint128) -> StdResult<Response> {
+fn try_set_max_mint_ratio(deps: DepsMut, info: MessageInfo, max_ratio: Decimal) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("Only admin can set max mint ratio"));
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set max mint ratio".to_string() });
     }
 
     config.max_mint_ratio = max_ratio;
@@ -182,316 +704,2948 @@ fn try_set_max_mint_ratio(deps: DepsMut, info: MessageInfo, max_ratio: Uint128)
         .add_attribute("max_ratio", max_ratio.to_string()))
 }
 
-fn try_set_paused(deps: DepsMut, info: MessageInfo, paused: bool) -> StdResult<Response> {
+fn try_set_min_burn_amount(deps: DepsMut, info: MessageInfo, amount: Uint128) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("Only admin can set pause status"));
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set min burn amount".to_string() });
     }
 
-    config.paused = paused;
+    config.min_burn_amount = amount;
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new()
-        .add_attribute("action", "set_paused")
-        .add_attribute("paused", paused.to_string()))
+        .add_attribute("action", "set_min_burn_amount")
+        .add_attribute("min_burn_amount", amount.to_string()))
 }
 
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetCw20Address {} => to_json_binary(&Cw20AddressResponse {
-            address: CONFIG.load(deps.storage)?.cw20_address,
-        }),
-        QueryMsg::GetConfig {} => {
-            let config = CONFIG.load(deps.storage)?;
+fn try_set_max_burn_per_tx(deps: DepsMut, info: MessageInfo, amount: Uint128) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set max burn per transaction".to_string() });
+    }
 
-            let calculated_ratio = if config.total_uluna_burned < Uint128::new(5_000_000_000_000) {
-                Uint128::one()
-            } else {
-                ((config.total_uluna_burned - Uint128::new(5_000_000_000_000)) / config.burn_threshold) + Uint128::new(2)
-            };
+    config.max_burn_per_tx = amount;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_max_burn_per_tx")
+        .add_attribute("max_burn_per_tx", amount.to_string()))
+}
 
-            let current_mint_ratio = if config.max_mint_ratio.is_zero() || calculated_ratio <= config.max_mint_ratio {
-                calculated_ratio
-            } else {
-                config.max_mint_ratio
-            };
+fn try_set_max_mint_per_address(deps: DepsMut, info: MessageInfo, amount: Uint128) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set max mint per address".to_string() });
+    }
 
-            to_json_binary(&ConfigResponse {
-                total_uluna_burned: config.total_uluna_burned,
-                total_tokens_minted: config.total_tokens_minted,
-                current_mint_ratio,
-                max_mint_ratio: config.max_mint_ratio,
-            })
-        },
+    config.max_mint_per_address = amount;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_max_mint_per_address")
+        .add_attribute("max_mint_per_address", amount.to_string()))
+}
+
+fn try_set_rate_limit(
+    deps: DepsMut,
+    info: MessageInfo,
+    rate_limit: Option<RateLimit>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set rate limit".to_string() });
+    }
+    if rate_limit.as_ref().is_some_and(|r| r.max_minted.is_zero() || r.window_seconds == 0) {
+        return Err(ContractError::InvalidRateLimit {});
+    }
+
+    let attribute = match &rate_limit {
+        Some(r) => format!("{}/{}s", r.max_minted, r.window_seconds),
+        None => "none".to_string(),
+    };
+    // A replaced limit keeps the usage recorded in the current window
+    if rate_limit.is_none() {
+        RATE_LIMIT_WINDOW.remove(deps.storage);
     }
+    config.rate_limit = rate_limit;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_rate_limit")
+        .add_attribute("rate_limit", attribute))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_json, Addr};
+fn try_lower_max_total_minted(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_total_minted: Uint128,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "lower the supply cap".to_string() });
+    }
+    if let Some(current) = config.max_total_minted {
+        if max_total_minted > current {
+            return Err(ContractError::SupplyCapIncrease { current, requested: max_total_minted });
+        }
+    }
+    if max_total_minted < config.lifetime_tokens_minted {
+        return Err(ContractError::InvalidSupplyCap { total_tokens_minted: config.lifetime_tokens_minted });
+    }
 
-    const CW20_ADDR: &str = "terra1cw20address";
+    config.max_total_minted = Some(max_total_minted);
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "lower_max_total_minted")
+        .add_attribute("max_total_minted", max_total_minted.to_string()))
+}
 
-    #[test]
-    fn test_instantiate_default() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("admin", &[]);
+fn try_set_refund_remainder(
+    deps: DepsMut,
+    info: MessageInfo,
+    refund_remainder: bool,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set refund mode".to_string() });
+    }
 
-        let msg = InstantiateMsg {
-            cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: None,
-            initial_tokens_minted: None,
-        };
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    config.refund_remainder = refund_remainder;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_refund_remainder")
+        .add_attribute("refund_remainder", refund_remainder.to_string()))
+}
 
-        assert_eq!(res.attributes.len(), 7);
-        assert_eq!(res.attributes[0], ("action", "instantiate"));
-        assert_eq!(res.attributes[1], ("admin", "admin"));
-        assert_eq!(res.attributes[2], ("cw20_address", CW20_ADDR));
-        assert_eq!(res.attributes[3], ("initial_uluna_burned", "0"));
-        assert_eq!(res.attributes[4], ("initial_tokens_minted", "0"));
-        assert_eq!(res.attributes[5], ("burn_threshold", "1000000000000"));
-        assert_eq!(res.attributes[6], ("max_mint_ratio", "0"));
+fn try_set_paused(deps: DepsMut, info: MessageInfo, paused: bool) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set pause status".to_string() });
+    }
 
-        let config_res: ConfigResponse = from_json(&query(
-            deps.as_ref(),
-            env,
-            QueryMsg::GetConfig {},
-        ).unwrap()).unwrap();
-        assert_eq!(config_res.total_uluna_burned, Uint128::zero());
-        assert_eq!(config_res.total_tokens_minted, Uint128::zero());
-        assert_eq!(config_res.current_mint_ratio, Uint128::one());
-        assert_eq!(config_res.max_mint_ratio, Uint128::zero());
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_paused")
+        .add_attribute("paused", paused.to_string()))
+}
+
+fn try_set_burn_denom(deps: DepsMut, info: MessageInfo, denom: String) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set burn denom".to_string() });
     }
 
-    #[test]
-    fn test_instantiate_with_initial_values() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
-        let info = mock_info("admin", &[]);
+    validate_denom(&denom)?;
+    config.burn_denom = denom.clone();
+    CONFIG.save(deps.storage, &config)?;
+    BURN_DENOMS.save(deps.storage, &denom, &Empty {})?;
+    Ok(Response::new()
+        .add_attribute("action", "set_burn_denom")
+        .add_attribute("denom", denom))
+}
 
-        let initial_burn = Uint128::new(10_000_000_000_000);
-        let initial_minted = Uint128::new(1_000_000_000_000);
-        let msg = InstantiateMsg {
-            cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: Some(initial_burn),
-            initial_tokens_minted: Some(initial_minted),
+fn try_set_burn_target(deps: DepsMut, info: MessageInfo, target: BurnTarget) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set burn target".to_string() });
+    }
+
+    config.burn_target = validate_burn_target(deps.api, target)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_burn_target")
+        .add_attribute("target", config.burn_target.to_string()))
+}
+
+fn try_set_mint_curve(deps: DepsMut, info: MessageInfo, curve: MintCurve) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "set mint curve".to_string() });
+    }
+
+    curve.validate()?;
+    config.mint_curve = curve;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_curve")
+        .add_attribute("current_mint_ratio", config.mint_ratio()?.to_string()))
+}
+
+fn try_propose_new_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "propose a new admin".to_string() });
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    PENDING_ADMIN.save(deps.storage, &PendingAdmin {
+        address: new_admin.clone(),
+        expires,
+    })?;
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_admin")
+        .add_attribute("pending_admin", new_admin.to_string())
+        .add_attribute("expires", expires.to_string()))
+}
+
+fn try_accept_admin(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    if info.sender != pending.address {
+        return Err(ContractError::NotPendingAdmin { pending_admin: pending.address.to_string() });
+    }
+    if pending.expires.is_expired(&env.block) {
+        return Err(ContractError::AdminProposalExpired {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_admin = config.admin.replace(pending.address.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous_admin.map(|a| a.to_string()).unwrap_or_default())
+        .add_attribute("admin", pending.address.to_string()))
+}
+
+fn try_cancel_admin_proposal(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "cancel an admin proposal".to_string() });
+    }
+    if PENDING_ADMIN.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+
+    PENDING_ADMIN.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
+}
+
+fn try_renounce_admin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "renounce the admin role".to_string() });
+    }
+
+    config.admin = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender.to_string()))
+}
+
+/// Sends out funds of a denom other than the burn denom, e.g. ones sent alongside
+/// `Mint` before funds were validated strictly. Defaults to the whole balance.
+fn try_recover_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    amount: Option<Uint128>,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized { action: "recover funds".to_string() });
+    }
+    if denom == config.burn_denom || BURN_DENOMS.has(deps.storage, &denom) {
+        return Err(ContractError::CannotRecoverBurnDenom { denom });
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let amount = match amount {
+        Some(amount) => amount,
+        None => deps.querier.query_balance(&env.contract.address, &denom)?.amount,
+    };
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRecover { denom });
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom: denom.clone(), amount }],
+        })
+        .add_attribute("action", "recover_funds")
+        .add_attribute("denom", denom)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("recipient", recipient.to_string()))
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetCw20Address {} => to_json_binary(&Cw20AddressResponse {
+            address: CONFIG.load(deps.storage)?.cw20_address,
+        }),
+        QueryMsg::GetConfig {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let current_mint_ratio = config.mint_ratio()?;
+
+            to_json_binary(&ConfigResponse {
+                burn_denom: config.burn_denom,
+                burn_target: config.burn_target,
+                total_burned: config.total_burned,
+                total_tokens_minted: config.total_tokens_minted,
+                lifetime_tokens_minted: config.lifetime_tokens_minted,
+                current_mint_ratio,
+                max_mint_ratio: config.max_mint_ratio,
+                min_burn_amount: config.min_burn_amount,
+                refund_remainder: config.refund_remainder,
+                max_burn_per_tx: config.max_burn_per_tx,
+                max_mint_per_address: config.max_mint_per_address,
+                rate_limit: config.rate_limit,
+                max_total_minted: config.max_total_minted,
+                burn_threshold: config.burn_threshold,
+                mint_curve: config.mint_curve,
+                paused: config.paused,
+            })
+        },
+        QueryMsg::GetAdmin {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let pending = PENDING_ADMIN.may_load(deps.storage)?;
+            to_json_binary(&AdminResponse {
+                admin: config.admin,
+                pending_admin: pending.as_ref().map(|p| p.address.clone()),
+                pending_expires: pending.map(|p| p.expires),
+            })
+        },
+        QueryMsg::SimulateMint { amount, address } => {
+            let config = CONFIG.load(deps.storage)?;
+            let address = address.map(|address| deps.api.addr_validate(&address)).transpose()?;
+            let limits = MintLimits::load(deps.storage, &config, env.block.time, address.as_ref())?;
+            let calculation = calculate_mint(&config, &limits, amount)?;
+            let max_ratio_applied = !config.max_mint_ratio.is_zero()
+                && calculation.segments.iter().any(|s| s.ratio == config.max_mint_ratio);
+            to_json_binary(&SimulateMintResponse {
+                burn_amount: calculation.burn_amount,
+                refund_amount: calculation.refund_amount,
+                mint_amount: calculation.mint_amount,
+                mint_ratio: calculation.mint_ratio,
+                effective_ratio: Decimal::from_ratio(calculation.burn_amount, calculation.mint_amount),
+                ratio_after: calculation.ratio_after,
+                max_mint_ratio: config.max_mint_ratio,
+                max_ratio_applied,
+                segments: calculation.segments,
+            })
+        },
+        QueryMsg::ReverseSimulateMint { desired_tokens, address } => {
+            let config = CONFIG.load(deps.storage)?;
+            let address = address.map(|address| deps.api.addr_validate(&address)).transpose()?;
+            let limits = MintLimits::load(deps.storage, &config, env.block.time, address.as_ref())?;
+            let (burn_amount, calculation) = reverse_calculate_mint(&config, &limits, desired_tokens)?;
+            to_json_binary(&ReverseSimulateMintResponse {
+                burn_amount,
+                mint_amount: calculation.mint_amount,
+                mint_ratio: calculation.mint_ratio,
+                effective_ratio: Decimal::from_ratio(burn_amount, calculation.mint_amount),
+                ratio_after: calculation.ratio_after,
+            })
+        },
+        QueryMsg::MintHistory { address, start_after, limit } => {
+            let address = deps.api.addr_validate(&address)?;
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let records = MINT_HISTORY
+                .prefix(&address)
+                .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, record)| record))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&MintHistoryResponse { records })
+        },
+        QueryMsg::AddressTotals { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let totals: AddressTotals = address_totals().may_load(deps.storage, &address)?.unwrap_or_default();
+            to_json_binary(&AddressTotalsResponse {
+                address,
+                total_burned: totals.burned,
+                total_minted: totals.minted,
+                mint_count: totals.mint_count,
+            })
+        },
+        QueryMsg::MintAllowance { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let config = CONFIG.load(deps.storage)?;
+            let totals: AddressTotals = address_totals().may_load(deps.storage, &address)?.unwrap_or_default();
+            to_json_binary(&MintAllowanceResponse {
+                remaining: remaining_allowance(&config, &totals),
+                address,
+                total_minted: totals.minted,
+                max_mint_per_address: config.max_mint_per_address,
+                max_burn_per_tx: config.max_burn_per_tx,
+            })
+        },
+        QueryMsg::RateLimitStatus {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let response = match config.rate_limit {
+                Some(rate_limit) => {
+                    let window = RateLimitWindow::current(
+                        RATE_LIMIT_WINDOW.may_load(deps.storage)?,
+                        &rate_limit,
+                        env.block.time,
+                    );
+                    RateLimitStatusResponse {
+                        used: window.minted,
+                        remaining: Some(rate_limit.max_minted.saturating_sub(window.minted)),
+                        window_start: Some(window.start),
+                        resets_at: Some(window.start.plus_seconds(rate_limit.window_seconds)),
+                        rate_limit: Some(rate_limit),
+                    }
+                },
+                None => RateLimitStatusResponse {
+                    rate_limit: None,
+                    used: Uint128::zero(),
+                    remaining: None,
+                    window_start: None,
+                    resets_at: None,
+                },
+            };
+            to_json_binary(&response)
+        },
+        QueryMsg::TokenStatus {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let token_info: TokenInfoResponse =
+                deps.querier.query_wasm_smart(&config.cw20_address, &Cw20QueryMsg::TokenInfo {})?;
+            let minter: Option<MinterResponse> =
+                deps.querier.query_wasm_smart(&config.cw20_address, &Cw20QueryMsg::Minter {})?;
+
+            let is_minter = minter.as_ref().is_some_and(|m| m.minter == env.contract.address.as_str());
+            let cap = minter.as_ref().and_then(|m| m.cap);
+            let mut issues = vec![];
+            if !is_minter {
+                issues.push(TokenStatusIssue::NotMinter {});
+            }
+            if cap.is_some_and(|cap| token_info.total_supply >= cap) {
+                issues.push(TokenStatusIssue::CapReached {});
+            }
+            to_json_binary(&TokenStatusResponse {
+                cw20_address: config.cw20_address,
+                name: token_info.name,
+                symbol: token_info.symbol,
+                decimals: token_info.decimals,
+                total_supply: token_info.total_supply,
+                minter: minter.map(|m| m.minter),
+                cap,
+                is_minter,
+                issues,
+            })
+        },
+        QueryMsg::AllMints { start_after, limit, order } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let start_after = start_after.map(Bound::exclusive);
+            let (min, max, order) = match order.unwrap_or(OrderBy::Asc) {
+                OrderBy::Asc => (start_after, None, Order::Ascending),
+                OrderBy::Desc => (None, start_after, Order::Descending),
+            };
+            let mints = MINT_EVENTS
+                .range(deps.storage, min, max, order)
+                .take(limit)
+                .map(|item| item.map(|(_, event)| event))
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&AllMintsResponse { mints })
+        },
+        QueryMsg::TopBurners { limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let burners = address_totals()
+                .idx
+                .burned
+                .range(deps.storage, None, None, Order::Descending)
+                .take(limit)
+                .map(|item| {
+                    item.map(|(address, totals)| BurnerInfo {
+                        address,
+                        total_burned: totals.burned,
+                        total_minted: totals.minted,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_json_binary(&TopBurnersResponse { burners })
+        },
+        QueryMsg::BurnerRank { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let totals = address_totals().may_load(deps.storage, &address)?;
+            let rank = match &totals {
+                Some(totals) => {
+                    let ahead = address_totals()
+                        .idx
+                        .burned
+                        .prefix_range_raw(
+                            deps.storage,
+                            Some(PrefixBound::exclusive(totals.burned.u128())),
+                            None,
+                            Order::Ascending,
+                        )
+                        .take(MAX_RANK as usize)
+                        .count() as u64;
+                    (ahead < MAX_RANK).then_some(ahead + 1)
+                },
+                None => None,
+            };
+            let totals = totals.unwrap_or_default();
+            to_json_binary(&BurnerRankResponse {
+                address,
+                rank,
+                max_rank: MAX_RANK,
+                total_burned: totals.burned,
+                total_minted: totals.minted,
+            })
+        },
+    }
+}
+
+/// Checks a native denom against the Cosmos SDK rules: 3-128 characters, starting
+/// with a letter, followed by alphanumerics or one of `/:._-`.
+fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let mut chars = denom.chars();
+    let valid = (3..=128).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom { denom: denom.to_string() });
+    }
+    Ok(())
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    version
+        .parse()
+        .map_err(|_| ContractError::InvalidVersion { version: version.to_string() })
+}
+
+/// `Addr` deserializes from any string, so addresses coming in through messages
+/// are re-validated before a burn target is stored.
+fn validate_burn_target(api: &dyn Api, target: BurnTarget) -> StdResult<BurnTarget> {
+    match target {
+        BurnTarget::Address { address } => Ok(BurnTarget::Address {
+            address: api.addr_validate(address.as_str())?,
+        }),
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{
+        coin, coins, from_json, Addr, ContractResult, QuerierResult, SystemError, SystemResult, WasmQuery,
+    };
+    use std::str::FromStr;
+    use crate::curve::CurveTier;
+    use crate::state::LegacyConfig;
+
+    const CW20_ADDR: &str = "terra1cw20address";
+    const BURN_ADDR: &str = "terra1burnaddress";
+
+    /// Wasm query handler for a CW20 at `cw20` with the given minter, cap and supply.
+    fn mock_cw20(
+        cw20: &str,
+        minter: &str,
+        cap: Option<u128>,
+        supply: u128,
+    ) -> impl Fn(&WasmQuery) -> QuerierResult {
+        let (cw20, minter) = (cw20.to_string(), minter.to_string());
+        move |query| match query {
+            WasmQuery::Smart { contract_addr, msg } if *contract_addr == cw20 => {
+                let response = match from_json(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => to_json_binary(&TokenInfoResponse {
+                        name: "Burn Token".to_string(),
+                        symbol: "BURN".to_string(),
+                        decimals: 6,
+                        total_supply: Uint128::new(supply),
+                    }),
+                    Cw20QueryMsg::Minter {} => to_json_binary(&Some(MinterResponse {
+                        minter: minter.clone(),
+                        cap: cap.map(Uint128::new),
+                    })),
+                    _ => panic!("unexpected query"),
+                };
+                SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+            },
+            WasmQuery::Smart { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract { addr: contract_addr.clone() })
+            },
+            _ => panic!("unexpected query"),
+        }
+    }
+
+    #[test]
+    fn test_instantiate_default() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        assert_eq!(res.attributes.len(), 10);
+        assert_eq!(res.attributes[0], ("action", "instantiate"));
+        assert_eq!(res.attributes[1], ("admin", "admin"));
+        assert_eq!(res.attributes[2], ("cw20_address", CW20_ADDR));
+        assert_eq!(res.attributes[3], ("initial_burned", "0"));
+        assert_eq!(res.attributes[4], ("initial_tokens_minted", "0"));
+        assert_eq!(res.attributes[5], ("burn_threshold", "1000000000000"));
+        assert_eq!(res.attributes[6], ("max_mint_ratio", "0"));
+        assert_eq!(res.attributes[7], ("burn_denom", "uluna"));
+        assert_eq!(res.attributes[8], ("burn_target", "address:terra1burnaddress"));
+        assert_eq!(res.attributes[9], ("max_total_minted", "none"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.burn_denom, "uluna");
+        assert_eq!(config_res.total_burned, Uint128::zero());
+        assert_eq!(config_res.total_tokens_minted, Uint128::zero());
+        assert_eq!(config_res.current_mint_ratio, Decimal::one());
+        assert_eq!(config_res.max_mint_ratio, Decimal::zero());
+        assert!(!config_res.paused);
+    }
+
+    #[test]
+    fn test_instantiate_legacy_msg() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+
+        // Instantiate JSON from before the burn denom and target were configurable
+        let msg: InstantiateMsg = from_json(
+            br#"{"cw20_address":"terra1cw20address","initial_uluna_burned":"5","initial_tokens_minted":"7"}"#,
+        ).unwrap();
+        assert_eq!(msg.burn_denom, LEGACY_BURN_DENOM);
+        assert_eq!(msg.burn_target, BurnTarget::Address { address: Addr::unchecked(LEGACY_BURN_ADDRESS) });
+        assert_eq!(msg.initial_burned, Some(Uint128::new(5)));
+        instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.burn_denom, "uluna");
+        assert_eq!(config_res.total_burned, Uint128::new(5));
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(7));
+    }
+
+    #[test]
+    fn test_instantiate_with_initial_values() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+
+        let initial_burn = Uint128::new(10_000_000_000_000);
+        let initial_minted = Uint128::new(1_000_000_000_000);
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(initial_burn),
+            initial_tokens_minted: Some(initial_minted),
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        assert_eq!(res.attributes.len(), 10);
+        assert_eq!(res.attributes[3], ("initial_burned", "10000000000000"));
+        assert_eq!(res.attributes[4], ("initial_tokens_minted", "1000000000000"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.total_burned, initial_burn);
+        assert_eq!(config_res.total_tokens_minted, initial_minted);
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(7u128, 1u128));
+        assert_eq!(config_res.max_mint_ratio, Decimal::zero());
+    }
+
+    #[test]
+    fn test_mint_ratio_progression() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let big_burn = Uint128::new(5_000_000_000_000);
+        let user_info = mock_info("user", &coins(big_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "5000000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "1"));
+
+        let stats_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(stats_res.total_burned, Uint128::new(5_000_000_000_000));
+        assert_eq!(stats_res.total_tokens_minted, Uint128::new(5_000_000_000_000));
+        assert_eq!(stats_res.current_mint_ratio, Decimal::from_ratio(2u128, 1u128));
+        assert_eq!(stats_res.max_mint_ratio, Decimal::zero());
+
+        let next_burn = Uint128::new(1_000_000_000_000);
+        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+
+        let stats_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(stats_res.total_burned, Uint128::new(6_000_000_000_000));
+        assert_eq!(stats_res.total_tokens_minted, Uint128::new(5_500_000_000_000));
+        assert_eq!(stats_res.current_mint_ratio, Decimal::from_ratio(3u128, 1u128));
+        assert_eq!(stats_res.max_mint_ratio, Decimal::zero());
+
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+    }
+
+    #[test]
+    fn test_admin_only_functions() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let non_admin_info = mock_info("non_admin", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            non_admin_info.clone(),
+            ExecuteMsg::SetCw20Address {
+                address: "new_addr".to_string(),
+                tokens_minted: Some(Uint128::zero()),
+                skip_minter_check: Some(true),
+            },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set CW20 address".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            non_admin_info.clone(),
+            ExecuteMsg::UpdateMinter { new_minter: Some("new_minter".to_string()) },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "update minter".to_string() });
+
+        deps.querier.update_wasm(mock_cw20("new_addr", "someone_else", None, 0));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetCw20Address {
+                address: "new_addr".to_string(),
+                tokens_minted: Some(Uint128::zero()),
+                skip_minter_check: Some(true),
+            },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_cw20_address"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::UpdateMinter { new_minter: Some("new_minter".to_string()) },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "update_minter"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            non_admin_info.clone(),
+            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set burn threshold".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_burn_threshold"));
+        assert_eq!(res.attributes[1], ("threshold", "2000000000000"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            non_admin_info.clone(),
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::from_ratio(5u128, 1u128) },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set max mint ratio".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::from_ratio(5u128, 1u128) },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_max_mint_ratio"));
+        assert_eq!(res.attributes[1], ("max_ratio", "5"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            non_admin_info.clone(),
+            ExecuteMsg::SetPaused { paused: true },
+        );
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set pause status".to_string() });
+
+        let res = execute(
+            deps.as_mut(), 
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetPaused { paused: true },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_paused"));
+        assert_eq!(res.attributes[1], ("paused", "true"));
+
+        let user_info = mock_info("user", &coins(1_000_000_000_000u128, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::Paused {});
+
+        // The pause is reported even when the funds would be rejected
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(res.unwrap_err(), ContractError::Paused {});
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &coins(100, "uusd")), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(res.unwrap_err(), ContractError::Paused {});
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            admin_info,
+            ExecuteMsg::SetPaused { paused: false },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_paused"));
+        assert_eq!(res.attributes[1], ("paused", "false"));
+    }
+
+    #[test]
+    fn test_mint_with_max_ratio() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::from_ratio(3u128, 1u128) },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_max_mint_ratio"));
+        assert_eq!(res.attributes[1], ("max_ratio", "3"));
+
+        let big_burn = Uint128::new(5_000_000_000_000);
+        let user_info = mock_info("user", &coins(big_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "5000000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "1"));
+
+        let next_burn = Uint128::new(1_000_000_000_000);
+        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+
+        // A burn spanning far more thresholds than a mint has segments stays at the cap
+        let whale_info = mock_info("whale", &coins(150_000_000_000_000, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), whale_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "50000000000000"));
+        assert_eq!(res.attributes[11], ("tiers", "1"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.total_burned, Uint128::new(158_000_000_000_000));
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(56_166_666_666_666));
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(3u128, 1u128));
+        assert_eq!(config_res.max_mint_ratio, Decimal::from_ratio(3u128, 1u128));
+    }
+
+    #[test]
+    fn test_burn_threshold_update() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let next_burn = Uint128::new(1_000_000_000_000);
+        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_burn_threshold"));
+        assert_eq!(res.attributes[1], ("threshold", "2000000000000"));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+
+        let bigger_burn = Uint128::new(2_000_000_000_000);
+        let user_info = mock_info("user", &coins(bigger_burn.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "666666666666"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.total_burned, Uint128::new(9_000_000_000_000));
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(1_666_666_666_666));
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(4u128, 1u128));
+        assert_eq!(config_res.max_mint_ratio, Decimal::zero());
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(7_500_000_000_000)),
+            initial_tokens_minted: Some(Uint128::new(1_000_000_000_000)),
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+        let addr_res: Cw20AddressResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetCw20Address {},
+        ).unwrap()).unwrap();
+        assert_eq!(addr_res.address, Addr::unchecked(CW20_ADDR));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.total_burned, Uint128::new(7_500_000_000_000));
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(1_000_000_000_000));
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(4u128, 1u128));
+        assert_eq!(config_res.max_mint_ratio, Decimal::zero());
+    }
+
+    #[test]
+    fn test_admin_transfer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let new_admin_info = mock_info("new_admin", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            ExecuteMsg::ProposeNewAdmin { new_admin: "new_admin".to_string(), expires: None },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "propose a new admin".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            ExecuteMsg::AcceptAdmin {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NoPendingAdmin {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::ProposeNewAdmin { new_admin: "new_admin".to_string(), expires: None },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "propose_new_admin"));
+        assert_eq!(res.attributes[1], ("pending_admin", "new_admin"));
+
+        let admin_res: AdminResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetAdmin {},
+        ).unwrap()).unwrap();
+        assert_eq!(admin_res.admin, Some(Addr::unchecked("admin")));
+        assert_eq!(admin_res.pending_admin, Some(Addr::unchecked("new_admin")));
+        assert_eq!(admin_res.pending_expires, Some(Expiration::Never {}));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("someone_else", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NotPendingAdmin { pending_admin: "new_admin".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            ExecuteMsg::AcceptAdmin {},
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "accept_admin"));
+        assert_eq!(res.attributes[1], ("previous_admin", "admin"));
+        assert_eq!(res.attributes[2], ("admin", "new_admin"));
+
+        let admin_res: AdminResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetAdmin {},
+        ).unwrap()).unwrap();
+        assert_eq!(admin_res.admin, Some(Addr::unchecked("new_admin")));
+        assert_eq!(admin_res.pending_admin, None);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetPaused { paused: true },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set pause status".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            ExecuteMsg::RenounceAdmin {},
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "renounce_admin"));
+
+        let admin_res: AdminResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetAdmin {},
+        ).unwrap()).unwrap();
+        assert_eq!(admin_res.admin, None);
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            new_admin_info,
+            ExecuteMsg::SetPaused { paused: true },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set pause status".to_string() });
+    }
+
+    #[test]
+    fn test_admin_proposal_expiry_and_cancel() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::ProposeNewAdmin {
+                new_admin: "new_admin".to_string(),
+                expires: Some(Expiration::AtHeight(env.block.height)),
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidExpiration {});
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::ProposeNewAdmin {
+                new_admin: "new_admin".to_string(),
+                expires: Some(Expiration::AtHeight(env.block.height + 10)),
+            },
+        ).unwrap();
+
+        env.block.height += 10;
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("new_admin", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::AdminProposalExpired {});
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::CancelAdminProposal {},
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "cancel_admin_proposal"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::CancelAdminProposal {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NoPendingAdmin {});
+
+        let admin_res: AdminResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetAdmin {},
+        ).unwrap()).unwrap();
+        assert_eq!(admin_res.admin, Some(Addr::unchecked("admin")));
+        assert_eq!(admin_res.pending_admin, None);
+        assert_eq!(admin_res.pending_expires, None);
+    }
+
+    #[test]
+    fn test_mint_errors() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info, msg).unwrap();
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(res.unwrap_err(), ContractError::NoFunds { denom: "uluna".to_string() });
+
+        let user_info = mock_info("user", &coins(1_000_000, "uusd"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::WrongDenom { expected: "uluna".to_string(), received: "uusd".to_string() }
+        );
+
+        let user_info = mock_info("user", &[coin(1_000, "uluna"), coin(1_000, "uusd")]);
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::MultipleCoins { expected: "uluna".to_string(), received: "uluna,uusd".to_string() }
+        );
+
+        let user_info = mock_info("user", &[coin(1_000, "uluna"), coin(1_000, "uluna")]);
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert!(matches!(res.unwrap_err(), ContractError::MultipleCoins { .. }));
+
+        let user_info = mock_info("user", &coins(0, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(res.unwrap_err(), ContractError::NoFunds { denom: "uluna".to_string() });
+
+        let user_info = mock_info("user", &coins(1, "uluna"));
+        let res = execute(deps.as_mut(), env, user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::ZeroMintAmount { amount: Uint128::one(), ratio: Decimal::from_ratio(2u128, 1u128) }
+        );
+    }
+
+    #[test]
+    fn test_custom_burn_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "1atom".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert_eq!(res.unwrap_err(), ContractError::InvalidDenom { denom: "1atom".to_string() });
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uatom".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let user_info = mock_info("user", &coins(1_000, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::WrongDenom { expected: "uatom".to_string(), received: "uluna".to_string() }
+        );
+
+        let user_info = mock_info("user", &coins(1_000, "uatom"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: BURN_ADDR.to_string(),
+                amount: coins(1_000, "uatom"),
+            })
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetBurnDenom { denom: "uluna".to_string() },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set burn denom".to_string() }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetBurnDenom { denom: "u".to_string() },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidDenom { denom: "u".to_string() });
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetBurnDenom { denom: "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2".to_string() },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_burn_denom"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(
+            config_res.burn_denom,
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+        assert_eq!(config_res.total_burned, Uint128::new(1_000));
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let legacy = LegacyConfig {
+            cw20_address: Addr::unchecked(CW20_ADDR),
+            admin: Addr::unchecked("admin"),
+            total_uluna_burned: Uint128::new(7_500_000_000_000),
+            total_tokens_minted: Uint128::new(1_000_000_000_000),
+            burn_threshold: Uint128::new(1_000_000_000_000),
+            max_mint_ratio: Uint128::zero(),
+            paused: false,
+        };
+        LEGACY_CONFIG.save(deps.as_mut().storage, &legacy).unwrap();
+        assert!(CONFIG.load(deps.as_ref().storage).is_err());
+
+        let migrate_msg = MigrateMsg { burn_denom: None, burn_target: None };
+        let res = migrate(deps.as_mut(), env.clone(), migrate_msg.clone()).unwrap();
+        assert_eq!(res.attributes[0], ("action", "migrate"));
+        assert_eq!(res.attributes[1], ("previous_version", "0.1.0"));
+        assert_eq!(res.attributes[2], ("new_version", CONTRACT_VERSION));
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.admin, Some(Addr::unchecked("admin")));
+        assert_eq!(config.burn_denom, "uluna");
+        assert_eq!(
+            config.burn_target,
+            BurnTarget::Address { address: Addr::unchecked(LEGACY_BURN_ADDRESS) }
+        );
+        assert_eq!(config.total_burned, Uint128::new(7_500_000_000_000));
+
+        let version = CONTRACT.load(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        // Running it again leaves an already migrated config untouched
+        let res = migrate(deps.as_mut(), env, migrate_msg).unwrap();
+        assert_eq!(res.attributes[1], ("previous_version", CONTRACT_VERSION));
+        assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap(), config);
+    }
+
+    #[test]
+    fn test_migrate_legacy_config_with_overrides() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let legacy = LegacyConfig {
+            cw20_address: Addr::unchecked(CW20_ADDR),
+            admin: Addr::unchecked("admin"),
+            total_uluna_burned: Uint128::new(100),
+            total_tokens_minted: Uint128::new(100),
+            burn_threshold: Uint128::new(1_000_000_000_000),
+            max_mint_ratio: Uint128::zero(),
+            paused: true,
+        };
+        LEGACY_CONFIG.save(deps.as_mut().storage, &legacy).unwrap();
+
+        let migrate_msg = MigrateMsg {
+            burn_denom: Some("uatom".to_string()),
+            burn_target: Some(BurnTarget::ModuleBurn {}),
+        };
+        migrate(deps.as_mut(), env, migrate_msg).unwrap();
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.burn_denom, "uatom");
+        assert_eq!(config.burn_target, BurnTarget::ModuleBurn {});
+        assert!(config.paused);
+    }
+
+    #[test]
+    fn test_migrate_version_checks() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        let version = CONTRACT.load(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        let migrate_msg = MigrateMsg { burn_denom: None, burn_target: None };
+        set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.13.4").unwrap();
+        let res = migrate(deps.as_mut(), env.clone(), migrate_msg.clone());
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::InvalidContractName {
+                expected: CONTRACT_NAME.to_string(),
+                actual: "crates.io:cw20-base".to_string(),
+            }
+        );
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let res = migrate(deps.as_mut(), env.clone(), migrate_msg.clone());
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::CannotMigrateDowngrade {
+                stored: "99.0.0".to_string(),
+                current: CONTRACT_VERSION.to_string(),
+            }
+        );
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "not-a-version").unwrap();
+        let res = migrate(deps.as_mut(), env.clone(), migrate_msg.clone());
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::InvalidVersion { version: "not-a-version".to_string() }
+        );
+
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.0").unwrap();
+        let res = migrate(deps.as_mut(), env, migrate_msg).unwrap();
+        assert_eq!(res.attributes[1], ("previous_version", "0.1.0"));
+        assert_eq!(CONTRACT.load(deps.as_ref().storage).unwrap().version, CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn test_burn_targets() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked("") },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        assert!(instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).is_err());
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::ModuleBurn {},
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let user_info = mock_info("user", &coins(1_000, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Burn { amount: coins(1_000, "uluna") })
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            user_info.clone(),
+            ExecuteMsg::SetBurnTarget { target: BurnTarget::HoldInContract {} },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set burn target".to_string() }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetBurnTarget { target: BurnTarget::HoldInContract {} },
+        ).unwrap();
+        assert_eq!(res.attributes[1], ("target", "hold_in_contract"));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert!(matches!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute { .. })));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.burn_target, BurnTarget::HoldInContract {});
+    }
+
+    #[test]
+    fn test_set_mint_curve() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(1_500)),
+            initial_tokens_minted: None,
+            mint_curve: Some(MintCurve::Linear { initial: Decimal::percent(200), slope: Decimal::one() }),
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_str("2.0000000015").unwrap());
+
+        let tiered = MintCurve::Piecewise {
+            tiers: vec![
+                CurveTier { burned_threshold: Uint128::new(1_000), ratio: Decimal::percent(1_000) },
+                CurveTier { burned_threshold: Uint128::new(2_000), ratio: Decimal::percent(2_000) },
+            ],
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetMintCurve { curve: tiered.clone() },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set mint curve".to_string() }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetMintCurve { curve: MintCurve::Piecewise { tiers: vec![] } },
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::InvalidCurve { .. }));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetMintCurve { curve: tiered.clone() },
+        ).unwrap();
+        assert_eq!(res.attributes[0], ("action", "set_mint_curve"));
+        assert_eq!(res.attributes[1], ("current_mint_ratio", "10"));
+
+        let user_info = mock_info("user", &coins(1_000, "uluna"));
+        // Half of the burn falls below the 2_000 tier and half above it
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "75"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "10"));
+        assert_eq!(res.attributes[11], ("tiers", "2"));
+        assert_eq!(res.attributes[12], ("tier_0_burned", "500"));
+        assert_eq!(res.attributes[13], ("tier_0_ratio", "10"));
+        assert_eq!(res.attributes[14], ("tier_0_minted", "50"));
+        assert_eq!(res.attributes[15], ("tier_1_burned", "500"));
+        assert_eq!(res.attributes[16], ("tier_1_ratio", "20"));
+        assert_eq!(res.attributes[17], ("tier_1_minted", "25"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(20u128, 1u128));
+        assert_eq!(config_res.mint_curve, tiered);
+    }
+
+    #[test]
+    fn test_fractional_mint_ratio() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: Some(MintCurve::Step {
+                offset: Uint128::zero(),
+                base: Decimal::percent(105),
+                increment: Decimal::percent(5),
+            }),
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let user_info = mock_info("user", &coins(1_000_000_000_000, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "952380952380"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "1.05"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.current_mint_ratio, Decimal::percent(110));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::percent(108) },
+        ).unwrap();
+
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "925925925925"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "1.08"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.current_mint_ratio, Decimal::percent(108));
+        assert_eq!(config_res.max_mint_ratio, Decimal::percent(108));
+    }
+
+    #[test]
+    fn test_mint_split_across_tiers() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(4_500_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let user_info = mock_info("whale", &coins(2_000_000_000_000, "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "1166666666666"));
+        assert_eq!(res.attributes[3], ("mint_ratio", "1"));
+        assert_eq!(res.attributes[11], ("tiers", "3"));
+        assert_eq!(res.attributes[12], ("tier_0_burned", "500000000000"));
+        assert_eq!(res.attributes[14], ("tier_0_minted", "500000000000"));
+        assert_eq!(res.attributes[15], ("tier_1_burned", "1000000000000"));
+        assert_eq!(res.attributes[16], ("tier_1_ratio", "2"));
+        assert_eq!(res.attributes[17], ("tier_1_minted", "500000000000"));
+        assert_eq!(res.attributes[18], ("tier_2_burned", "500000000000"));
+        assert_eq!(res.attributes[19], ("tier_2_ratio", "3"));
+        assert_eq!(res.attributes[20], ("tier_2_minted", "166666666666"));
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.total_burned, Uint128::new(6_500_000_000_000));
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(1_166_666_666_666));
+        assert_eq!(config_res.current_mint_ratio, Decimal::from_ratio(3u128, 1u128));
+    }
+
+    #[test]
+    fn test_simulate_mint() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(4_500_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let amount = Uint128::new(2_000_000_000_000);
+        let sim: SimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateMint { amount, address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(sim.mint_amount, Uint128::new(1_166_666_666_666));
+        assert_eq!(sim.mint_ratio, Decimal::one());
+        assert_eq!(sim.effective_ratio, Decimal::from_ratio(amount, sim.mint_amount));
+        assert_eq!(sim.ratio_after, Decimal::from_ratio(3u128, 1u128));
+        assert!(!sim.max_ratio_applied);
+        assert_eq!(sim.segments.len(), 3);
+
+        // The actual mint matches the simulation exactly
+        let user_info = mock_info("user", &coins(amount.u128(), "uluna"));
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", sim.mint_amount.to_string().as_str()));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::from_ratio(3u128, 1u128) },
+        ).unwrap();
+        let sim: SimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateMint { amount, address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(sim.mint_amount, Uint128::new(666_666_666_666));
+        assert_eq!(sim.ratio_after, Decimal::from_ratio(3u128, 1u128));
+        assert!(sim.max_ratio_applied);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetPaused { paused: true },
+        ).unwrap();
+        let err = query(deps.as_ref(), env, QueryMsg::SimulateMint { amount, address: None }).unwrap_err();
+        assert_eq!(err, StdError::generic_err(ContractError::Paused {}.to_string()));
+    }
+
+    #[test]
+    fn test_reverse_simulate_mint() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(4_500_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        // 500B at ratio 1, 1T at ratio 2 and the remaining 166_666_666_666 tokens at ratio 3
+        let desired_tokens = Uint128::new(1_166_666_666_666);
+        let rev: ReverseSimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens, address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(rev.burn_amount, Uint128::new(1_999_999_999_998));
+        assert_eq!(rev.mint_amount, desired_tokens);
+        assert_eq!(rev.mint_ratio, Decimal::one());
+        assert_eq!(rev.ratio_after, Decimal::from_ratio(3u128, 1u128));
+
+        // One unit less falls short of the target
+        let sim: SimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateMint { amount: rev.burn_amount - Uint128::one(), address: None },
+        ).unwrap()).unwrap();
+        assert!(sim.mint_amount < desired_tokens);
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetMaxMintRatio { max_ratio: Decimal::from_ratio(2u128, 1u128) },
+        ).unwrap();
+        let rev: ReverseSimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(1_000_000_000_000), address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(rev.burn_amount, Uint128::new(1_500_000_000_000));
+        assert_eq!(rev.mint_amount, Uint128::new(1_000_000_000_000));
+
+        let err = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::zero(), address: None },
+        ).unwrap_err();
+        assert_eq!(err, StdError::generic_err(ContractError::ZeroDesiredTokens {}.to_string()));
+    }
+
+    #[test]
+    fn test_mint_history() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        for amount in [1_000u128, 2_000, 3_000] {
+            let user_info = mock_info("user", &coins(amount, "uluna"));
+            execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+            env.block.height += 1;
+        }
+        let other_info = mock_info("other", &coins(10_000, "uluna"));
+        execute(deps.as_mut(), env.clone(), other_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+
+        let history: MintHistoryResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintHistory { address: "user".to_string(), start_after: None, limit: Some(2) },
+        ).unwrap()).unwrap();
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(
+            history.records[0],
+            MintRecord {
+                id: 0,
+                recipient: Addr::unchecked("user"),
+                height: 12_345,
+                time: env.block.time,
+                burned: Uint128::new(1_000),
+                minted: Uint128::new(500),
+                ratio: Decimal::from_ratio(2u128, 1u128),
+            }
+        );
+        assert_eq!(history.records[1].id, 1);
+        assert_eq!(history.records[1].height, 12_346);
+
+        let history: MintHistoryResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintHistory { address: "user".to_string(), start_after: Some(1), limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].id, 2);
+        assert_eq!(history.records[0].burned, Uint128::new(3_000));
+
+        let totals: AddressTotalsResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AddressTotals { address: "user".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(totals.total_burned, Uint128::new(6_000));
+        assert_eq!(totals.total_minted, Uint128::new(3_000));
+        assert_eq!(totals.mint_count, 3);
+
+        let totals: AddressTotalsResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::AddressTotals { address: "nobody".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(totals.total_burned, Uint128::zero());
+        assert_eq!(totals.mint_count, 0);
+    }
+
+    #[test]
+    fn test_all_mints() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        for (sender, amount) in [("alice", 100u128), ("bob", 200), ("alice", 300), ("carol", 400)] {
+            let info = mock_info(sender, &coins(amount, "uluna"));
+            execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+            env.block.height += 1;
+        }
+
+        let res: AllMintsResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AllMints { start_after: None, limit: Some(3), order: None },
+        ).unwrap()).unwrap();
+        assert_eq!(res.mints.iter().map(|m| m.id).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(
+            res.mints[1],
+            MintEvent {
+                id: 1,
+                sender: Addr::unchecked("bob"),
+                recipient: Addr::unchecked("bob"),
+                burned: Uint128::new(200),
+                minted: Uint128::new(200),
+                ratio: Decimal::one(),
+                height: 12_346,
+                time: env.block.time,
+            }
+        );
+
+        let res: AllMintsResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AllMints { start_after: Some(2), limit: None, order: Some(OrderBy::Asc) },
+        ).unwrap()).unwrap();
+        assert_eq!(res.mints.iter().map(|m| m.id).collect::<Vec<_>>(), vec![3]);
+
+        let res: AllMintsResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::AllMints { start_after: Some(3), limit: Some(2), order: Some(OrderBy::Desc) },
+        ).unwrap()).unwrap();
+        assert_eq!(res.mints.iter().map(|m| m.id).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(res.mints[0].sender, Addr::unchecked("alice"));
+    }
+
+    #[test]
+    fn test_burner_leaderboard() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        for (sender, amount) in [("alice", 100u128), ("bob", 500), ("carol", 300), ("alice", 250), ("dave", 300)] {
+            let info = mock_info(sender, &coins(amount, "uluna"));
+            execute(deps.as_mut(), env.clone(), info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        }
+
+        let res: TopBurnersResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::TopBurners { limit: Some(2) },
+        ).unwrap()).unwrap();
+        assert_eq!(
+            res.burners,
+            vec![
+                BurnerInfo {
+                    address: Addr::unchecked("bob"),
+                    total_burned: Uint128::new(500),
+                    total_minted: Uint128::new(500),
+                },
+                BurnerInfo {
+                    address: Addr::unchecked("alice"),
+                    total_burned: Uint128::new(350),
+                    total_minted: Uint128::new(350),
+                },
+            ]
+        );
+
+        let res: TopBurnersResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::TopBurners { limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(res.burners.len(), 4);
+
+        let rank = |address: &str| -> BurnerRankResponse {
+            from_json(query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::BurnerRank { address: address.to_string() },
+            ).unwrap()).unwrap()
+        };
+        assert_eq!(rank("bob").rank, Some(1));
+        assert_eq!(rank("alice").rank, Some(2));
+        assert_eq!(rank("alice").total_burned, Uint128::new(350));
+        // Equal burns share a rank
+        assert_eq!(rank("carol").rank, Some(3));
+        assert_eq!(rank("dave").rank, Some(3));
+        assert_eq!(rank("erin").rank, None);
+    }
+
+    #[test]
+    fn test_mint_to_recipient() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let alice_info = mock_info("alice", &coins(1_000, "uluna"));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            alice_info.clone(),
+            ExecuteMsg::Mint {
+                recipient: Some("X".to_string()),
+                min_tokens_out: None,
+                deadline: None,
+            },
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::Std(_)));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            alice_info,
+            ExecuteMsg::Mint {
+                recipient: Some("bob".to_string()),
+                min_tokens_out: None,
+                deadline: None,
+            },
+        ).unwrap();
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: "bob".to_string(),
+                    amount: Uint128::new(1_000),
+                }).unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(res.attributes[8], ("burner", "alice"));
+        assert_eq!(res.attributes[9], ("recipient", "bob"));
+
+        let history: MintHistoryResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintHistory { address: "alice".to_string(), start_after: None, limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(history.records[0].recipient, Addr::unchecked("bob"));
+
+        let res: AllMintsResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AllMints { start_after: None, limit: None, order: None },
+        ).unwrap()).unwrap();
+        assert_eq!(res.mints[0].sender, Addr::unchecked("alice"));
+        assert_eq!(res.mints[0].recipient, Addr::unchecked("bob"));
+
+        // Burn totals stay with the burner, not the recipient
+        let totals: AddressTotalsResponse = from_json(query(
+            deps.as_ref(),
+            env,
+            QueryMsg::AddressTotals { address: "bob".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(totals.mint_count, 0);
+    }
+
+    #[test]
+    fn test_mint_and_send() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        let hook = Binary::from(br#"{"stake":{}}"#.to_vec());
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(1_000, "uluna")),
+            ExecuteMsg::MintAndSend {
+                contract: "vault".to_string(),
+                msg: hook.clone(),
+                min_tokens_out: None,
+                deadline: None,
+            },
+        ).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: env.contract.address.to_string(),
+                    amount: Uint128::new(1_000),
+                }).unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            res.messages[2].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: "vault".to_string(),
+                    amount: Uint128::new(1_000),
+                    msg: hook.clone(),
+                }).unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(res.attributes[8], ("burner", "alice"));
+        assert_eq!(res.attributes[9], ("recipient", "vault"));
+
+        let history: MintHistoryResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintHistory { address: "alice".to_string(), start_after: None, limit: None },
+        ).unwrap()).unwrap();
+        assert_eq!(history.records[0].recipient, Addr::unchecked("vault"));
+
+        // Funds are checked the same way as for a plain mint
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("alice", &[]),
+            ExecuteMsg::MintAndSend {
+                contract: "vault".to_string(),
+                msg: hook,
+                min_tokens_out: None,
+                deadline: None,
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::NoFunds { denom: "uluna".to_string() });
+    }
+
+    #[test]
+    fn test_mint_slippage_and_deadline() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        let user_info = mock_info("user", &coins(1_000, "uluna"));
+
+        // Ratio is 2, so 1000 burned mints 500
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            user_info.clone(),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: Some(Uint128::new(501)), deadline: None },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::SlippageExceeded { minted: Uint128::new(500), min_tokens_out: Uint128::new(501) }
+        );
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.total_burned, Uint128::new(5_000_000_000_000));
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            user_info.clone(),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: Some(Uint128::new(500)), deadline: None },
+        ).unwrap();
+
+        let deadline = Expiration::AtHeight(env.block.height + 1);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            user_info.clone(),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: Some(deadline) },
+        ).unwrap();
+
+        env.block.height += 1;
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            user_info.clone(),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: Some(deadline) },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::DeadlineExpired { deadline });
+
+        let deadline = Expiration::AtTime(env.block.time);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            user_info,
+            ExecuteMsg::MintAndSend {
+                contract: "vault".to_string(),
+                msg: Binary::default(),
+                min_tokens_out: None,
+                deadline: Some(deadline),
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::DeadlineExpired { deadline });
+    }
+
+    #[test]
+    fn test_recover_funds() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::HoldInContract {},
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+        deps.querier.update_balance(
+            env.contract.address.clone(),
+            vec![coin(5_000, "uluna"), coin(300, "uusd")],
+        );
+
+        let recover = |denom: &str, amount: Option<u128>| ExecuteMsg::RecoverFunds {
+            denom: denom.to_string(),
+            amount: amount.map(Uint128::new),
+            recipient: "owner".to_string(),
+        };
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), recover("uusd", None));
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "recover funds".to_string() });
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), recover("uluna", None));
+        assert_eq!(res.unwrap_err(), ContractError::CannotRecoverBurnDenom { denom: "uluna".to_string() });
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), recover("ukrw", None));
+        assert_eq!(res.unwrap_err(), ContractError::NothingToRecover { denom: "ukrw".to_string() });
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), recover("uusd", None)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "owner".to_string(), amount: coins(300, "uusd") })
+        );
+
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), recover("uusd", Some(100))).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "owner".to_string(), amount: coins(100, "uusd") })
+        );
+
+        // Funds burned under a previous burn denom stay locked after switching
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::SetBurnDenom { denom: "uusd".to_string() },
+        ).unwrap();
+        let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), recover("uluna", None));
+        assert_eq!(res.unwrap_err(), ContractError::CannotRecoverBurnDenom { denom: "uluna".to_string() });
+        let res = execute(deps.as_mut(), env, mock_info("admin", &[]), recover("uusd", None));
+        assert_eq!(res.unwrap_err(), ContractError::CannotRecoverBurnDenom { denom: "uusd".to_string() });
+    }
+
+    #[test]
+    fn test_min_burn_amount() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetMinBurnAmount { amount: Uint128::new(100) },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set min burn amount".to_string() }
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetMinBurnAmount { amount: Uint128::new(100) },
+        ).unwrap();
+
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.min_burn_amount, Uint128::new(100));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(99, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::BurnBelowMinimum { amount: Uint128::new(99), min_burn_amount: Uint128::new(100) }
+        );
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::SimulateMint { amount: Uint128::new(99), address: None });
+        assert!(res.is_err());
+
+        // The reverse simulation never suggests a burn below the minimum
+        let res: ReverseSimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(10), address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(res.burn_amount, Uint128::new(100));
+        assert_eq!(res.mint_amount, Uint128::new(100));
+
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("user", &coins(100, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_refund_remainder() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+        let user_info = mock_info("user", &coins(1_001, "uluna"));
+
+        // Off by default: the whole amount is burned
+        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.attributes[1], ("burn_amount", "1001"));
+        assert_eq!(res.attributes[10], ("refunded", "0"));
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetRefundRemainder { refund_remainder: true },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set refund mode".to_string() }
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetRefundRemainder { refund_remainder: true },
+        ).unwrap();
+
+        let sim: SimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateMint { amount: Uint128::new(1_001), address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(sim.burn_amount, Uint128::new(1_000));
+        assert_eq!(sim.refund_amount, Uint128::new(1));
+        assert_eq!(sim.mint_amount, Uint128::new(500));
+        assert_eq!(sim.effective_ratio, Decimal::from_ratio(2u128, 1u128));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: BURN_ADDR.to_string(), amount: coins(1_000, "uluna") })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "user".to_string(), amount: coins(1, "uluna") })
+        );
+        assert_eq!(res.attributes[1], ("burn_amount", "1000"));
+        assert_eq!(res.attributes[2], ("mint_amount", "500"));
+        assert_eq!(res.attributes[10], ("refunded", "1"));
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.total_burned, Uint128::new(5_000_000_002_001));
+
+        // Exact multiples leave nothing to refund
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("user", &coins(1_000, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        ).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(res.attributes[10], ("refunded", "0"));
+    }
+
+    #[test]
+    fn test_mint_limits() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+
+        let allowance: MintAllowanceResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintAllowance { address: "user".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(allowance.remaining, None);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetMaxBurnPerTx { amount: Uint128::new(1_000) },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set max burn per transaction".to_string() }
+        );
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetMaxMintPerAddress { amount: Uint128::new(1_500) },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Unauthorized { action: "set max mint per address".to_string() }
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetMaxBurnPerTx { amount: Uint128::new(1_000) },
+        ).unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            ExecuteMsg::SetMaxMintPerAddress { amount: Uint128::new(1_500) },
+        ).unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(1_001, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::BurnAboveMaximum { amount: Uint128::new(1_001), max_burn_per_tx: Uint128::new(1_000) }
+        );
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::SimulateMint { amount: Uint128::new(1_001), address: None });
+        assert!(res.is_err());
+
+        let user_info = mock_info("user", &coins(1_000, "uluna"));
+        execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None }).unwrap();
+
+        let allowance: MintAllowanceResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::MintAllowance { address: "user".to_string() },
+        ).unwrap()).unwrap();
+        assert_eq!(allowance.total_minted, Uint128::new(1_000));
+        assert_eq!(allowance.remaining, Some(Uint128::new(500)));
+        assert_eq!(allowance.max_burn_per_tx, Uint128::new(1_000));
+
+        let res = execute(deps.as_mut(), env.clone(), user_info, ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None });
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::AddressMintLimitExceeded { minted: Uint128::new(1_000), remaining: Uint128::new(500) }
+        );
+
+        // Simulating for the burner applies its allowance, anonymous simulations do not
+        let simulate = |address: Option<&str>| QueryMsg::SimulateMint {
+            amount: Uint128::new(1_000),
+            address: address.map(str::to_string),
         };
-        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let res = query(deps.as_ref(), env.clone(), simulate(Some("user")));
+        assert!(res.unwrap_err().to_string().contains("remaining allowance of 500"));
+        query(deps.as_ref(), env.clone(), simulate(None)).unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(501), address: Some("user".to_string()) },
+        );
+        assert!(res.is_err());
+        let res: ReverseSimulateMintResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(500), address: Some("user".to_string()) },
+        ).unwrap()).unwrap();
+        assert_eq!(res.burn_amount, Uint128::new(500));
 
-        assert_eq!(res.attributes.len(), 7);
-        assert_eq!(res.attributes[3], ("initial_uluna_burned", "10000000000000"));
-        assert_eq!(res.attributes[4], ("initial_tokens_minted", "1000000000000"));
+        // The limit applies to the burner, whoever receives the tokens
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(500, "uluna")),
+            ExecuteMsg::Mint { recipient: Some("other".to_string()), min_tokens_out: None, deadline: None },
+        ).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500"));
 
-        let config_res: ConfigResponse = from_json(&query(
+        let allowance: MintAllowanceResponse = from_json(query(
             deps.as_ref(),
             env,
-            QueryMsg::GetConfig {},
+            QueryMsg::MintAllowance { address: "user".to_string() },
         ).unwrap()).unwrap();
-        assert_eq!(config_res.total_uluna_burned, initial_burn);
-        assert_eq!(config_res.total_tokens_minted, initial_minted);
-        assert_eq!(config_res.current_mint_ratio, Uint128::new(7));
-        assert_eq!(config_res.max_mint_ratio, Uint128::zero());
+        assert_eq!(allowance.remaining, Some(Uint128::zero()));
     }
 
     #[test]
-    fn test_mint_ratio_progression() {
+    fn test_rate_limit() {
         let mut deps = mock_dependencies();
-        let env = mock_env();
+        let mut env = mock_env();
         let admin_info = mock_info("admin", &[]);
 
         let msg = InstantiateMsg {
             cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: None,
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
             initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
         };
         instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
 
-        let big_burn = Uint128::new(5_000_000_000_000);
-        let user_info = mock_info("user", &coins(big_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "5000000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "1"));
+        let status: RateLimitStatusResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RateLimitStatus {},
+        ).unwrap()).unwrap();
+        assert_eq!(status.rate_limit, None);
+        assert_eq!(status.remaining, None);
+
+        let rate_limit = RateLimit { max_minted: Uint128::new(1_000), window_seconds: 86_400 };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::SetRateLimit { rate_limit: Some(rate_limit.clone()) },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "set rate limit".to_string() });
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetRateLimit { rate_limit: Some(RateLimit { max_minted: Uint128::new(1_000), window_seconds: 0 }) },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidRateLimit {});
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::SetRateLimit { rate_limit: Some(rate_limit.clone()) },
+        ).unwrap();
 
-        let stats_res: ConfigResponse = from_json(&query(
+        let window_start = env.block.time;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(600, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        ).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(3_600);
+        let status: RateLimitStatusResponse = from_json(query(
             deps.as_ref(),
             env.clone(),
-            QueryMsg::GetConfig {},
+            QueryMsg::RateLimitStatus {},
         ).unwrap()).unwrap();
-        assert_eq!(stats_res.total_uluna_burned, Uint128::new(5_000_000_000_000));
-        assert_eq!(stats_res.total_tokens_minted, Uint128::new(5_000_000_000_000));
-        assert_eq!(stats_res.current_mint_ratio, Uint128::new(2));
-        assert_eq!(stats_res.max_mint_ratio, Uint128::zero());
+        assert_eq!(status.used, Uint128::new(600));
+        assert_eq!(status.remaining, Some(Uint128::new(400)));
+        assert_eq!(status.window_start, Some(window_start));
+        assert_eq!(status.resets_at, Some(window_start.plus_seconds(86_400)));
 
-        let next_burn = Uint128::new(1_000_000_000_000);
-        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+        // The limit is global, so another address cannot exceed it either
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &coins(500, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        );
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::RateLimitExceeded {
+                minted: Uint128::new(500),
+                remaining: Uint128::new(400),
+                resets_at: window_start.plus_seconds(86_400),
+            }
+        );
 
-        let stats_res: ConfigResponse = from_json(&query(
+        // The simulations see the same window
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::SimulateMint { amount: Uint128::new(500), address: None });
+        assert!(res.unwrap_err().to_string().contains("rate limit window"));
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(401), address: None });
+        assert!(res.is_err());
+        let res: ReverseSimulateMintResponse = from_json(query(
             deps.as_ref(),
             env.clone(),
-            QueryMsg::GetConfig {},
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(400), address: None },
         ).unwrap()).unwrap();
-        assert_eq!(stats_res.total_uluna_burned, Uint128::new(6_000_000_000_000));
-        assert_eq!(stats_res.total_tokens_minted, Uint128::new(5_500_000_000_000));
-        assert_eq!(stats_res.current_mint_ratio, Uint128::new(3));
-        assert_eq!(stats_res.max_mint_ratio, Uint128::zero());
+        assert_eq!(res.burn_amount, Uint128::new(400));
 
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+        // Once the window has run out the full capacity is available again
+        env.block.time = window_start.plus_seconds(86_400);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &coins(1_000, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        ).unwrap();
+        let status: RateLimitStatusResponse = from_json(query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RateLimitStatus {},
+        ).unwrap()).unwrap();
+        assert_eq!(status.remaining, Some(Uint128::zero()));
+        assert_eq!(status.window_start, Some(env.block.time));
+
+        execute(deps.as_mut(), env.clone(), admin_info, ExecuteMsg::SetRateLimit { rate_limit: None }).unwrap();
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("bob", &coins(1_000, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        ).unwrap();
     }
 
     #[test]
-    fn test_admin_only_functions() {
+    fn test_supply_cap() {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let admin_info = mock_info("admin", &[]);
 
         let msg = InstantiateMsg {
             cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: None,
-            initial_tokens_minted: None,
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: Some(Uint128::new(500)),
+            mint_curve: None,
+            max_total_minted: Some(Uint128::new(400)),
+        };
+        let res = instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg);
+        assert_eq!(res.unwrap_err(), ContractError::InvalidSupplyCap { total_tokens_minted: Uint128::new(500) });
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: Some(Uint128::new(500)),
+            mint_curve: None,
+            max_total_minted: Some(Uint128::new(2_000)),
         };
         instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
 
-        let non_admin_info = mock_info("non_admin", &[]);
         let res = execute(
             deps.as_mut(),
             env.clone(),
-            non_admin_info.clone(),
-            ExecuteMsg::SetCw20Address { address: "new_addr".to_string() },
+            admin_info.clone(),
+            ExecuteMsg::LowerMaxTotalMinted { max_total_minted: Uint128::new(2_001) },
         );
-        assert!(res.is_err());
         assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Only admin can set CW20 address"
+            res.unwrap_err(),
+            ContractError::SupplyCapIncrease { current: Uint128::new(2_000), requested: Uint128::new(2_001) }
         );
-
         let res = execute(
             deps.as_mut(),
             env.clone(),
-            non_admin_info.clone(),
-            ExecuteMsg::UpdateMinter { new_minter: "new_minter".to_string() },
+            mock_info("user", &[]),
+            ExecuteMsg::LowerMaxTotalMinted { max_total_minted: Uint128::new(1_500) },
         );
-        assert!(res.is_err());
-        assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Only admin can update minter"
-        );
-
-        let res = execute(
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized { action: "lower the supply cap".to_string() });
+        execute(
             deps.as_mut(),
             env.clone(),
             admin_info.clone(),
-            ExecuteMsg::SetCw20Address { address: "new_addr".to_string() },
+            ExecuteMsg::LowerMaxTotalMinted { max_total_minted: Uint128::new(1_500) },
         ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_cw20_address"));
 
-        let res = execute(
-            deps.as_mut(),
+        let config_res: ConfigResponse = from_json(query(
+            deps.as_ref(),
             env.clone(),
-            admin_info.clone(),
-            ExecuteMsg::UpdateMinter { new_minter: "new_minter".to_string() },
-        ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "update_minter"));
+            QueryMsg::GetConfig {},
+        ).unwrap()).unwrap();
+        assert_eq!(config_res.max_total_minted, Some(Uint128::new(1_500)));
 
-        let res = execute(
-            deps.as_mut(),
+        // Only 1000 tokens are left, so a burn of 1200 is partially filled
+        let sim: SimulateMintResponse = from_json(query(
+            deps.as_ref(),
             env.clone(),
-            non_admin_info.clone(),
-            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
+            QueryMsg::SimulateMint { amount: Uint128::new(1_200), address: None },
+        ).unwrap()).unwrap();
+        assert_eq!(sim.mint_amount, Uint128::new(1_000));
+        assert_eq!(sim.burn_amount, Uint128::new(1_000));
+        assert_eq!(sim.refund_amount, Uint128::new(200));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ReverseSimulateMint { desired_tokens: Uint128::new(1_001), address: None },
         );
         assert!(res.is_err());
-        assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Only admin can set burn threshold"
-        );
 
         let res = execute(
             deps.as_mut(),
             env.clone(),
-            admin_info.clone(),
-            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
+            mock_info("user", &coins(1_200, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
         ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_burn_threshold"));
-        assert_eq!(res.attributes[1], ("threshold", "2000000000000"));
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address: "user".to_string(), amount: coins(200, "uluna") })
+        );
+        assert_eq!(res.attributes[2], ("mint_amount", "1000"));
+        assert_eq!(res.attributes[10], ("refunded", "200"));
 
         let res = execute(
             deps.as_mut(),
             env.clone(),
-            non_admin_info.clone(),
-            ExecuteMsg::SetMaxMintRatio { max_ratio: Uint128::new(5) },
+            mock_info("user", &coins(100, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
         );
-        assert!(res.is_err());
+        assert_eq!(res.unwrap_err(), ContractError::SupplyCapReached { max_total_minted: Uint128::new(1_500) });
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            admin_info,
+            ExecuteMsg::LowerMaxTotalMinted { max_total_minted: Uint128::new(1_499) },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::InvalidSupplyCap { total_tokens_minted: Uint128::new(1_500) });
+    }
+
+    #[test]
+    fn test_token_status() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
+            initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+        deps.querier.update_wasm(mock_cw20(CW20_ADDR, env.contract.address.as_str(), None, 1_000));
+        let status: TokenStatusResponse = from_json(query(deps.as_ref(), env.clone(), QueryMsg::TokenStatus {}).unwrap()).unwrap();
+        assert_eq!(status.symbol, "BURN");
+        assert_eq!(status.decimals, 6);
+        assert_eq!(status.total_supply, Uint128::new(1_000));
+        assert_eq!(status.cap, None);
+        assert!(status.is_minter);
+        assert!(status.issues.is_empty());
+
+        deps.querier.update_wasm(mock_cw20(CW20_ADDR, "someone_else", Some(1_000), 1_000));
+        let status: TokenStatusResponse = from_json(query(deps.as_ref(), env, QueryMsg::TokenStatus {}).unwrap()).unwrap();
+        assert_eq!(status.minter, Some("someone_else".to_string()));
+        assert_eq!(status.cap, Some(Uint128::new(1_000)));
+        assert!(!status.is_minter);
+        assert_eq!(status.issues, vec![TokenStatusIssue::NotMinter {}, TokenStatusIssue::CapReached {}]);
+    }
+
+    #[test]
+    fn test_set_cw20_address_checks() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let admin_info = mock_info("admin", &[]);
+        const NEW_CW20: &str = "terra1newcw20";
+
+        let msg = InstantiateMsg {
+            cw20_address: CW20_ADDR.to_string(),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: Some(Uint128::new(5_000_000_000_000)),
+            initial_tokens_minted: Some(Uint128::new(1_000)),
+            mint_curve: None,
+            max_total_minted: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+        let set_address = |address: &str, tokens_minted: Option<u128>| ExecuteMsg::SetCw20Address {
+            address: address.to_string(),
+            tokens_minted: tokens_minted.map(Uint128::new),
+            skip_minter_check: None,
+        };
+
+        // Not a CW20 at all
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address("terra1typo", Some(0)));
+        assert_eq!(res.unwrap_err(), ContractError::NotCw20 { address: "terra1typo".to_string() });
+
+        deps.querier.update_wasm(mock_cw20(NEW_CW20, "someone_else", None, 500));
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(NEW_CW20, None));
+        assert_eq!(res.unwrap_err(), ContractError::TokensMintedRequired {});
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(NEW_CW20, Some(0)));
+        assert_eq!(res.unwrap_err(), ContractError::NotCw20Minter { address: NEW_CW20.to_string() });
+
+        deps.querier.update_wasm(mock_cw20(NEW_CW20, env.contract.address.as_str(), None, 500));
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(NEW_CW20, Some(501)));
         assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Only admin can set max mint ratio"
+            res.unwrap_err(),
+            ContractError::TokensMintedAboveSupply { tokens_minted: Uint128::new(501), total_supply: Uint128::new(500) }
         );
 
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(NEW_CW20, Some(500))).unwrap();
+        assert_eq!(res.attributes[1], ("previous_cw20_address", CW20_ADDR));
+        assert_eq!(res.attributes[3], ("total_tokens_minted", "500"));
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config.cw20_address, Addr::unchecked(NEW_CW20));
+        assert_eq!(config.total_tokens_minted, Uint128::new(500));
+        assert_eq!(config.total_burned, Uint128::new(5_000_000_000_000));
+
+        // The counter cannot be reset without switching tokens
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(NEW_CW20, Some(0)));
+        assert_eq!(res.unwrap_err(), ContractError::TokensMintedUnchanged {});
         let res = execute(
             deps.as_mut(),
             env.clone(),
             admin_info.clone(),
-            ExecuteMsg::SetMaxMintRatio { max_ratio: Uint128::new(5) },
-        ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_max_mint_ratio"));
-        assert_eq!(res.attributes[1], ("max_ratio", "5"));
+            ExecuteMsg::SetCw20Address {
+                address: NEW_CW20.to_string(),
+                tokens_minted: Some(Uint128::zero()),
+                skip_minter_check: Some(true),
+            },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::TokensMintedUnchanged {});
+        assert_eq!(CONFIG.load(&deps.storage).unwrap().total_tokens_minted, Uint128::new(500));
 
+        // The override skips the minter check, but a token change still needs a valid counter
         let res = execute(
             deps.as_mut(),
             env.clone(),
-            non_admin_info.clone(),
-            ExecuteMsg::SetPaused { paused: true },
-        );
-        assert!(res.is_err());
-        assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Only admin can set pause status"
+            admin_info.clone(),
+            ExecuteMsg::SetCw20Address { address: "terra1typo".to_string(), tokens_minted: None, skip_minter_check: Some(true) },
         );
-
+        assert_eq!(res.unwrap_err(), ContractError::TokensMintedRequired {});
         let res = execute(
-            deps.as_mut(), 
+            deps.as_mut(),
             env.clone(),
             admin_info.clone(),
-            ExecuteMsg::SetPaused { paused: true },
-        ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_paused"));
-        assert_eq!(res.attributes[1], ("paused", "true"));
-
-        let user_info = mock_info("user", &coins(1_000_000_000_000u128, "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {});
-        assert!(res.is_err());
-        assert_eq!(
-            res.unwrap_err().to_string(),
-            "Generic error: Minting is currently paused"
+            ExecuteMsg::SetCw20Address {
+                address: "terra1typo".to_string(),
+                tokens_minted: Some(Uint128::zero()),
+                skip_minter_check: Some(true),
+            },
         );
+        assert_eq!(res.unwrap_err(), ContractError::NotCw20 { address: "terra1typo".to_string() });
 
-        let res = execute(
+        deps.querier.update_wasm(mock_cw20(CW20_ADDR, "someone_else", None, 0));
+        execute(
             deps.as_mut(),
             env,
             admin_info,
-            ExecuteMsg::SetPaused { paused: false },
+            ExecuteMsg::SetCw20Address {
+                address: CW20_ADDR.to_string(),
+                tokens_minted: Some(Uint128::zero()),
+                skip_minter_check: Some(true),
+            },
         ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_paused"));
-        assert_eq!(res.attributes[1], ("paused", "false"));
     }
 
     #[test]
-    fn test_mint_with_max_ratio() {
+    fn test_update_minter() {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let admin_info = mock_info("admin", &[]);
 
         let msg = InstantiateMsg {
             cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: None,
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
             initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: None,
         };
         instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
 
@@ -499,119 +3653,138 @@ mod tests {
             deps.as_mut(),
             env.clone(),
             admin_info.clone(),
-            ExecuteMsg::SetMaxMintRatio { max_ratio: Uint128::new(3) },
-        ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_max_mint_ratio"));
-        assert_eq!(res.attributes[1], ("max_ratio", "3"));
-
-        let big_burn = Uint128::new(5_000_000_000_000);
-        let user_info = mock_info("user", &coins(big_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "5000000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "1"));
-
-        let next_burn = Uint128::new(1_000_000_000_000);
-        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+            ExecuteMsg::UpdateMinter { new_minter: Some("X".to_string()) },
+        );
+        assert!(matches!(res.unwrap_err(), ContractError::Std(_)));
 
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+        // Keeping the role here leaves minting open
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::UpdateMinter { new_minter: Some(env.contract.address.to_string()) },
+        ).unwrap();
+        assert_eq!(res.attributes[2], ("paused", "false"));
 
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "333333333333"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            ExecuteMsg::UpdateMinter { new_minter: Some("new_minter".to_string()) },
+        ).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::UpdateMinter { new_minter: Some("new_minter".to_string()) })
+                    .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(res.attributes[1], ("new_minter", "new_minter"));
+        assert_eq!(res.attributes[2], ("paused", "true"));
 
-        let config_res: ConfigResponse = from_json(&query(
+        let config_res: ConfigResponse = from_json(query(
             deps.as_ref(),
-            env,
+            env.clone(),
             QueryMsg::GetConfig {},
         ).unwrap()).unwrap();
-        assert_eq!(config_res.total_uluna_burned, Uint128::new(8_000_000_000_000));
-        assert_eq!(config_res.total_tokens_minted, Uint128::new(6_166_666_666_666));
-        assert_eq!(config_res.current_mint_ratio, Uint128::new(3));
-        assert_eq!(config_res.max_mint_ratio, Uint128::new(3));
+        assert!(config_res.paused);
+
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &coins(1_000, "uluna")),
+            ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None },
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Paused {});
+
+        // Renouncing sends a null minter
+        let res = execute(deps.as_mut(), env, admin_info, ExecuteMsg::UpdateMinter { new_minter: None }).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW20_ADDR.to_string(),
+                msg: Binary::from(br#"{"update_minter":{"new_minter":null}}"#.to_vec()),
+                funds: vec![],
+            })
+        );
+        assert_eq!(res.attributes[1], ("new_minter", "none"));
     }
 
     #[test]
-    fn test_burn_threshold_update() {
+    fn test_supply_cap_survives_token_change() {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let admin_info = mock_info("admin", &[]);
+        const NEW_CW20: &str = "terra1newcw20";
 
         let msg = InstantiateMsg {
             cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: Some(Uint128::new(5_000_000_000_000)),
+            burn_denom: "uluna".to_string(),
+            burn_target: BurnTarget::Address { address: Addr::unchecked(BURN_ADDR) },
+            initial_burned: None,
             initial_tokens_minted: None,
+            mint_curve: None,
+            max_total_minted: Some(Uint128::new(1_500)),
         };
         instantiate(deps.as_mut(), env.clone(), admin_info.clone(), msg).unwrap();
+        let mint = ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None };
+        let set_address = |address: &str, tokens_minted: u128| ExecuteMsg::SetCw20Address {
+            address: address.to_string(),
+            tokens_minted: Some(Uint128::new(tokens_minted)),
+            skip_minter_check: Some(true),
+        };
 
-        let next_burn = Uint128::new(1_000_000_000_000);
-        let user_info = mock_info("user", &coins(next_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+        execute(deps.as_mut(), env.clone(), mock_info("user", &coins(1_000, "uluna")), mint.clone()).unwrap();
 
-        let res = execute(
-            deps.as_mut(),
-            env.clone(),
-            admin_info.clone(),
-            ExecuteMsg::SetBurnThreshold { threshold: Uint128::new(2_000_000_000_000) },
-        ).unwrap();
-        assert_eq!(res.attributes[0], ("action", "set_burn_threshold"));
-        assert_eq!(res.attributes[1], ("threshold", "2000000000000"));
+        deps.querier.update_wasm(mock_cw20(CW20_ADDR, env.contract.address.as_str(), None, 1_000));
+        let res = execute(deps.as_mut(), env.clone(), admin_info.clone(), set_address(CW20_ADDR, 0));
+        assert_eq!(res.unwrap_err(), ContractError::TokensMintedUnchanged {});
 
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "500000000000"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "2"));
+        // A fresh token starts its own count, but the cap room already used stays used
+        deps.querier.update_wasm(mock_cw20(NEW_CW20, env.contract.address.as_str(), None, 0));
+        execute(deps.as_mut(), env.clone(), admin_info, set_address(NEW_CW20, 0)).unwrap();
 
-        let bigger_burn = Uint128::new(2_000_000_000_000);
-        let user_info = mock_info("user", &coins(bigger_burn.u128(), "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), user_info.clone(), ExecuteMsg::Mint {}).unwrap();
-        assert_eq!(res.attributes[2], ("mint_amount", "666666666666"));
-        assert_eq!(res.attributes[3], ("mint_ratio", "3"));
+        // Only the 500 tokens left under the cap can still be minted
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &coins(1_000, "uluna")), mint.clone()).unwrap();
+        assert_eq!(res.attributes[2], ("mint_amount", "500"));
+        let res = execute(deps.as_mut(), env.clone(), mock_info("user", &coins(1_000, "uluna")), mint);
+        assert_eq!(res.unwrap_err(), ContractError::SupplyCapReached { max_total_minted: Uint128::new(1_500) });
 
-        let config_res: ConfigResponse = from_json(&query(
+        let config_res: ConfigResponse = from_json(query(
             deps.as_ref(),
             env,
             QueryMsg::GetConfig {},
         ).unwrap()).unwrap();
-        assert_eq!(config_res.total_uluna_burned, Uint128::new(9_000_000_000_000));
-        assert_eq!(config_res.total_tokens_minted, Uint128::new(1_666_666_666_666));
-        assert_eq!(config_res.current_mint_ratio, Uint128::new(4));
-        assert_eq!(config_res.max_mint_ratio, Uint128::zero());
+        assert_eq!(config_res.total_tokens_minted, Uint128::new(500));
+        assert_eq!(config_res.lifetime_tokens_minted, Uint128::new(1_500));
     }
 
     #[test]
-    fn test_queries() {
+    fn test_burner_rank_is_bounded() {
         let mut deps = mock_dependencies();
         let env = mock_env();
-        let info = mock_info("admin", &[]);
 
-        let msg = InstantiateMsg {
-            cw20_address: CW20_ADDR.to_string(),
-            initial_uluna_burned: Some(Uint128::new(7_500_000_000_000)),
-            initial_tokens_minted: Some(Uint128::new(1_000_000_000_000)),
+        for i in 0..MAX_RANK as u128 {
+            let burner = Addr::unchecked(format!("burner{}", i));
+            let totals = AddressTotals { burned: Uint128::new(1_000 + i), minted: Uint128::zero(), mint_count: 1 };
+            address_totals().save(&mut deps.storage, &burner, &totals).unwrap();
+        }
+        let low = AddressTotals { burned: Uint128::new(1), minted: Uint128::zero(), mint_count: 1 };
+        address_totals().save(&mut deps.storage, &Addr::unchecked("low"), &low).unwrap();
+
+        let rank = |address: &str| -> BurnerRankResponse {
+            from_json(query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::BurnerRank { address: address.to_string() },
+            ).unwrap()).unwrap()
         };
-        instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-
-        let addr_res: Cw20AddressResponse = from_json(&query(
-            deps.as_ref(),
-            env.clone(),
-            QueryMsg::GetCw20Address {},
-        ).unwrap()).unwrap();
-        assert_eq!(addr_res.address, Addr::unchecked(CW20_ADDR));
-
-        let config_res: ConfigResponse = from_json(&query(
-            deps.as_ref(),
-            env,
-            QueryMsg::GetConfig {},
-        ).unwrap()).unwrap();
-        assert_eq!(config_res.total_uluna_burned, Uint128::new(7_500_000_000_000));
-        assert_eq!(config_res.total_tokens_minted, Uint128::new(1_000_000_000_000));
-        assert_eq!(config_res.current_mint_ratio, Uint128::new(4));
-        assert_eq!(config_res.max_mint_ratio, Uint128::zero());
+        assert_eq!(rank("burner0").rank, Some(MAX_RANK));
+        let res = rank("low");
+        assert_eq!(res.rank, None);
+        assert_eq!(res.max_rank, MAX_RANK);
+        assert_eq!(res.total_burned, Uint128::new(1));
     }
 }
@@ -0,0 +1,459 @@
+use cosmwasm_std::{
+    CheckedMultiplyRatioError, Decimal, DivideByZeroError, OverflowError, OverflowOperation,
+    StdError, StdResult, Uint128,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+
+/// Upper bound on the ratio segments a single mint is split into, keeping gas bounded
+/// for very large burns on curves with a small `burn_threshold`.
+pub const MAX_MINT_SEGMENTS: usize = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CurveTier {
+    pub burned_threshold: Uint128,
+    pub ratio: Decimal,
+}
+
+/// Maps the total amount burned so far to the mint ratio (burned units per minted token).
+/// Every curve except `Piecewise` advances once per `burn_threshold` burned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MintCurve {
+    /// Ratio 1 below `offset`, then `base` plus `increment` for every full `burn_threshold`
+    /// burned past it
+    Step { offset: Uint128, base: Decimal, increment: Decimal },
+    /// `initial` plus `slope` per `burn_threshold` burned, interpolated continuously
+    Linear { initial: Decimal, slope: Decimal },
+    /// Issuance shrinks by `rate` (e.g. 0.05 for 5%) for every full `burn_threshold` burned
+    ExponentialDecay { initial: Decimal, rate: Decimal },
+    /// Ratio of the highest tier whose `burned_threshold` has been reached, 1 below the first tier
+    Piecewise { tiers: Vec<CurveTier> },
+}
+
+impl Default for MintCurve {
+    /// The stepped schedule the contract shipped with: ratio 2 from 5T burned,
+    /// rising by one every `burn_threshold`.
+    fn default() -> Self {
+        MintCurve::Step {
+            offset: Uint128::new(5_000_000_000_000),
+            base: Decimal::from_ratio(2u128, 1u128),
+            increment: Decimal::one(),
+        }
+    }
+}
+
+/// Part of a mint charged at a single ratio.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintSegment {
+    pub burned: Uint128,
+    pub ratio: Decimal,
+    pub minted: Uint128,
+}
+
+impl MintCurve {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let invalid = |reason: &str| Err(ContractError::InvalidCurve { reason: reason.to_string() });
+        match self {
+            MintCurve::Step { base, .. } if base.is_zero() => invalid("step base must be nonzero"),
+            MintCurve::Linear { initial, .. } if initial.is_zero() => {
+                invalid("linear initial ratio must be nonzero")
+            },
+            MintCurve::ExponentialDecay { initial, rate } => {
+                if initial.is_zero() {
+                    invalid("exponential decay initial ratio must be nonzero")
+                } else if *rate >= Decimal::one() {
+                    invalid("exponential decay rate must be below 1")
+                } else {
+                    Ok(())
+                }
+            },
+            MintCurve::Piecewise { tiers } => {
+                if tiers.is_empty() {
+                    return invalid("piecewise curve needs at least one tier");
+                }
+                if tiers.iter().any(|t| t.ratio.is_zero()) {
+                    return invalid("piecewise tier ratios must be nonzero");
+                }
+                if tiers.windows(2).any(|w| w[0].burned_threshold >= w[1].burned_threshold) {
+                    return invalid("piecewise tiers must be sorted by strictly increasing burned_threshold");
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Uncapped ratio once `burned` units have been burned in total. Ratios too large
+    /// for a `Decimal` saturate at `Decimal::MAX`.
+    pub fn ratio(&self, burned: Uint128, burn_threshold: Uint128) -> StdResult<Decimal> {
+        let ratio = match self {
+            MintCurve::Step { offset, base, increment } => {
+                if burned < *offset {
+                    Decimal::one()
+                } else {
+                    let steps = (burned - *offset).checked_div(burn_threshold)?;
+                    base.saturating_add(increment.saturating_mul(saturating_decimal(steps)))
+                }
+            },
+            MintCurve::Linear { initial, slope } => {
+                if burn_threshold.is_zero() {
+                    return Err(DivideByZeroError::new(burned).into());
+                }
+                let progress = Decimal::checked_from_ratio(burned, burn_threshold).unwrap_or(Decimal::MAX);
+                initial.saturating_add(slope.saturating_mul(progress))
+            },
+            MintCurve::ExponentialDecay { initial, rate } => {
+                let steps = burned.checked_div(burn_threshold)?;
+                let steps = u32::try_from(steps.u128()).unwrap_or(u32::MAX);
+                // Issuance per unit is (1 - rate)^steps / initial; once that rounds
+                // down to nothing the ratio saturates.
+                match (Decimal::one() - *rate).checked_pow(steps) {
+                    Ok(factor) if !factor.is_zero() => {
+                        initial.checked_div(factor).unwrap_or(Decimal::MAX)
+                    },
+                    _ => Decimal::MAX,
+                }
+            },
+            MintCurve::Piecewise { tiers } => tiers
+                .iter()
+                .rev()
+                .find(|t| burned >= t.burned_threshold)
+                .map(|t| t.ratio)
+                .unwrap_or_else(Decimal::one),
+        };
+        Ok(ratio)
+    }
+
+    /// Burned total at which the ratio next changes, or `None` if it stays the same
+    /// from `burned` on. `Linear` is cut at every multiple of `burn_threshold`.
+    fn next_boundary(&self, burned: Uint128, burn_threshold: Uint128) -> StdResult<Option<Uint128>> {
+        let boundary = match self {
+            MintCurve::Step { offset, increment, .. } => {
+                if burned < *offset {
+                    Some(*offset)
+                } else if increment.is_zero() {
+                    None
+                } else {
+                    let steps = (burned - *offset).checked_div(burn_threshold)?;
+                    steps
+                        .checked_add(Uint128::one())
+                        .and_then(|s| s.checked_mul(burn_threshold))
+                        .and_then(|d| d.checked_add(*offset))
+                        .ok()
+                }
+            },
+            MintCurve::Linear { slope, .. } if slope.is_zero() => None,
+            MintCurve::ExponentialDecay { rate, .. } if rate.is_zero() => None,
+            MintCurve::Linear { .. } | MintCurve::ExponentialDecay { .. } => {
+                let steps = burned.checked_div(burn_threshold)?;
+                steps
+                    .checked_add(Uint128::one())
+                    .and_then(|s| s.checked_mul(burn_threshold))
+                    .ok()
+            },
+            MintCurve::Piecewise { tiers } => tiers
+                .iter()
+                .map(|t| t.burned_threshold)
+                .find(|threshold| *threshold > burned),
+        };
+        Ok(boundary)
+    }
+
+    /// Whether the ratio never drops below `ratio` once `burned` units have been burned.
+    fn stays_at_or_above(&self, burned: Uint128, ratio: Decimal) -> bool {
+        match self {
+            // Only the drop from 1 to `base` at `offset` can lower a step curve
+            MintCurve::Step { offset, base, .. } => burned >= *offset || *base >= ratio,
+            MintCurve::Linear { .. } | MintCurve::ExponentialDecay { .. } => true,
+            MintCurve::Piecewise { tiers } => tiers
+                .iter()
+                .filter(|t| t.burned_threshold > burned)
+                .all(|t| t.ratio >= ratio),
+        }
+    }
+
+    /// Highest ratio reached while the burned total moves from `from` up to `last`.
+    fn highest_ratio(&self, from: Uint128, last: Uint128, burn_threshold: Uint128) -> StdResult<Decimal> {
+        let highest = self.ratio(from, burn_threshold)?.max(self.ratio(last, burn_threshold)?);
+        let highest = match self {
+            MintCurve::Piecewise { tiers } => tiers
+                .iter()
+                .filter(|t| t.burned_threshold > from && t.burned_threshold <= last)
+                .fold(highest, |highest, t| highest.max(t.ratio)),
+            // The other curves only fall at a step curve's offset, so their highest
+            // ratio is at one of the ends
+            _ => highest,
+        };
+        Ok(highest)
+    }
+
+    /// Splits a burn of `amount` on top of `burned` into the ratio segments it crosses,
+    /// so each portion is minted at the ratio that applies to it rather than the whole
+    /// burn at the starting ratio. Segments are priced at their midpoint (for `Linear`
+    /// that is the average ratio over the segment), capped by `max_ratio` unless it is
+    /// zero. Adjacent segments ending up at the same ratio are merged.
+    ///
+    /// Once the ratio is constant or held at the cap for good, the rest of the burn is a
+    /// single segment. A burn still crossing more than `MAX_MINT_SEGMENTS` boundaries has
+    /// its rest charged as one last segment at the highest ratio it reaches, so the
+    /// contract never issues more than the curve allows.
+    pub fn split_mint(
+        &self,
+        burned: Uint128,
+        amount: Uint128,
+        burn_threshold: Uint128,
+        max_ratio: Decimal,
+    ) -> StdResult<Vec<MintSegment>> {
+        let ceiling = if max_ratio.is_zero() { Decimal::MAX } else { max_ratio };
+        let mut segments: Vec<MintSegment> = vec![];
+        let mut position = burned;
+        let mut remaining = amount;
+        while !remaining.is_zero() {
+            let current = self.ratio(position, burn_threshold)?;
+            let boundary = if current >= ceiling && self.stays_at_or_above(position, ceiling) {
+                None
+            } else {
+                self.next_boundary(position, burn_threshold)?
+            };
+            let (size, ratio) = match boundary {
+                Some(boundary) if segments.len() + 1 < MAX_MINT_SEGMENTS => {
+                    let size = (boundary - position).min(remaining);
+                    let midpoint = position + size / Uint128::new(2);
+                    (size, self.ratio(midpoint, burn_threshold)?)
+                },
+                Some(_) => {
+                    let last = position.checked_add(remaining - Uint128::one())?;
+                    (remaining, self.highest_ratio(position, last, burn_threshold)?)
+                },
+                None => (remaining, current),
+            };
+            let ratio = cap_ratio(ratio, max_ratio);
+
+            match segments.last_mut() {
+                Some(last) if last.ratio == ratio => {
+                    last.burned += size;
+                    last.minted = tokens_for_burn(last.burned, ratio)?;
+                },
+                _ => segments.push(MintSegment {
+                    burned: size,
+                    ratio,
+                    minted: tokens_for_burn(size, ratio)?,
+                }),
+            }
+
+            position = position.checked_add(size)?;
+            remaining -= size;
+        }
+        Ok(segments)
+    }
+}
+
+/// Applies `max_ratio` to a curve ratio; a zero `max_ratio` means no cap.
+pub fn cap_ratio(ratio: Decimal, max_ratio: Decimal) -> Decimal {
+    if max_ratio.is_zero() {
+        ratio
+    } else {
+        ratio.min(max_ratio)
+    }
+}
+
+/// Tokens minted for burning `amount` at `ratio`. Always rounds down, so the
+/// contract never issues more than the curve allows; the dropped fraction is
+/// less than one token.
+pub fn tokens_for_burn(amount: Uint128, ratio: Decimal) -> StdResult<Uint128> {
+    amount
+        .checked_multiply_ratio(Decimal::one().atomics(), ratio.atomics())
+        .map_err(|e| match e {
+            CheckedMultiplyRatioError::DivideByZero => DivideByZeroError::new(amount).into(),
+            CheckedMultiplyRatioError::Overflow => {
+                StdError::overflow(OverflowError::new(OverflowOperation::Mul, amount, ratio))
+            },
+        })
+}
+
+fn saturating_decimal(value: Uint128) -> Decimal {
+    Decimal::checked_from_ratio(value, 1u128).unwrap_or(Decimal::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Uint128 = Uint128::new(1_000_000_000_000);
+
+    fn dec(value: u128) -> Decimal {
+        Decimal::from_ratio(value, 1u128)
+    }
+
+    #[test]
+    fn test_default_step_curve() {
+        let curve = MintCurve::default();
+        assert_eq!(curve.ratio(Uint128::zero(), THRESHOLD).unwrap(), dec(1));
+        assert_eq!(curve.ratio(Uint128::new(4_999_999_999_999), THRESHOLD).unwrap(), dec(1));
+        assert_eq!(curve.ratio(Uint128::new(5_000_000_000_000), THRESHOLD).unwrap(), dec(2));
+        assert_eq!(curve.ratio(Uint128::new(7_500_000_000_000), THRESHOLD).unwrap(), dec(4));
+    }
+
+    #[test]
+    fn test_fractional_step_curve() {
+        let curve = MintCurve::Step {
+            offset: Uint128::zero(),
+            base: Decimal::one(),
+            increment: Decimal::percent(5),
+        };
+        assert_eq!(curve.ratio(Uint128::zero(), THRESHOLD).unwrap(), Decimal::one());
+        assert_eq!(curve.ratio(THRESHOLD, THRESHOLD).unwrap(), Decimal::percent(105));
+        assert_eq!(curve.ratio(Uint128::new(2_999_999_999_999), THRESHOLD).unwrap(), Decimal::percent(110));
+    }
+
+    #[test]
+    fn test_linear_and_exponential_curves() {
+        let linear = MintCurve::Linear { initial: dec(2), slope: dec(3) };
+        assert_eq!(linear.ratio(Uint128::zero(), THRESHOLD).unwrap(), dec(2));
+        assert_eq!(linear.ratio(Uint128::new(2_500_000_000_000), THRESHOLD).unwrap(), Decimal::percent(950));
+        assert!(linear.ratio(THRESHOLD, Uint128::zero()).is_err());
+
+        let decay = MintCurve::ExponentialDecay {
+            initial: dec(10),
+            rate: Decimal::percent(50),
+        };
+        assert_eq!(decay.ratio(Uint128::zero(), THRESHOLD).unwrap(), dec(10));
+        assert_eq!(decay.ratio(Uint128::new(3_000_000_000_000), THRESHOLD).unwrap(), dec(80));
+        assert_eq!(decay.ratio(Uint128::MAX, THRESHOLD).unwrap(), Decimal::MAX);
+    }
+
+    #[test]
+    fn test_piecewise_curve() {
+        let curve = MintCurve::Piecewise {
+            tiers: vec![
+                CurveTier { burned_threshold: Uint128::new(100), ratio: Decimal::percent(150) },
+                CurveTier { burned_threshold: Uint128::new(500), ratio: dec(5) },
+            ],
+        };
+        curve.validate().unwrap();
+        assert_eq!(curve.ratio(Uint128::new(99), THRESHOLD).unwrap(), dec(1));
+        assert_eq!(curve.ratio(Uint128::new(100), THRESHOLD).unwrap(), Decimal::percent(150));
+        assert_eq!(curve.ratio(Uint128::new(10_000), THRESHOLD).unwrap(), dec(5));
+    }
+
+    #[test]
+    fn test_invalid_curves() {
+        let unsorted = MintCurve::Piecewise {
+            tiers: vec![
+                CurveTier { burned_threshold: Uint128::new(500), ratio: dec(2) },
+                CurveTier { burned_threshold: Uint128::new(500), ratio: dec(5) },
+            ],
+        };
+        assert!(matches!(unsorted.validate(), Err(ContractError::InvalidCurve { .. })));
+        assert!(MintCurve::Piecewise { tiers: vec![] }.validate().is_err());
+        assert!(MintCurve::Linear { initial: Decimal::zero(), slope: dec(1) }.validate().is_err());
+        assert!(MintCurve::ExponentialDecay { initial: dec(1), rate: Decimal::one() }
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_split_mint_across_steps() {
+        let curve = MintCurve::default();
+        let segments = curve
+            .split_mint(Uint128::new(4_500_000_000_000), Uint128::new(2_000_000_000_000), THRESHOLD, Decimal::zero())
+            .unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                MintSegment {
+                    burned: Uint128::new(500_000_000_000),
+                    ratio: dec(1),
+                    minted: Uint128::new(500_000_000_000),
+                },
+                MintSegment {
+                    burned: Uint128::new(1_000_000_000_000),
+                    ratio: dec(2),
+                    minted: Uint128::new(500_000_000_000),
+                },
+                MintSegment {
+                    burned: Uint128::new(500_000_000_000),
+                    ratio: dec(3),
+                    minted: Uint128::new(166_666_666_666),
+                },
+            ]
+        );
+
+        // Everything past the cap is charged at the same ratio, so it collapses into one segment
+        let segments = curve
+            .split_mint(Uint128::new(5_000_000_000_000), Uint128::new(5_000_000_000_000), THRESHOLD, dec(3))
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].burned, Uint128::new(1_000_000_000_000));
+        assert_eq!(segments[1].burned, Uint128::new(4_000_000_000_000));
+        assert_eq!(segments[1].ratio, dec(3));
+    }
+
+    #[test]
+    fn test_split_mint_linear_and_piecewise() {
+        let linear = MintCurve::Linear { initial: dec(1), slope: dec(1) };
+        let segments = linear
+            .split_mint(Uint128::zero(), Uint128::new(2_000_000_000_000), THRESHOLD, Decimal::zero())
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].ratio, Decimal::percent(150));
+        assert_eq!(segments[1].ratio, Decimal::percent(250));
+
+        let piecewise = MintCurve::Piecewise {
+            tiers: vec![CurveTier { burned_threshold: Uint128::new(100), ratio: dec(4) }],
+        };
+        let segments = piecewise
+            .split_mint(Uint128::new(50), Uint128::new(1_000), THRESHOLD, Decimal::zero())
+            .unwrap();
+        assert_eq!(segments[0], MintSegment { burned: Uint128::new(50), ratio: dec(1), minted: Uint128::new(50) });
+        assert_eq!(segments[1], MintSegment { burned: Uint128::new(950), ratio: dec(4), minted: Uint128::new(237) });
+
+    }
+
+    #[test]
+    fn test_split_mint_long_burns() {
+        // Capped at 3 from 6T on: a burn of 150 thresholds is two segments
+        let curve = MintCurve::default();
+        let segments = curve
+            .split_mint(Uint128::new(5_000_000_000_000), Uint128::new(150_000_000_000_000), THRESHOLD, dec(3))
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].burned, Uint128::new(149_000_000_000_000));
+        assert_eq!(segments[1].ratio, dec(3));
+        assert_eq!(segments[1].minted, Uint128::new(49_666_666_666_666));
+
+        // A constant ratio never needs splitting
+        let flat = MintCurve::ExponentialDecay { initial: dec(2), rate: Decimal::zero() };
+        let segments = flat.split_mint(Uint128::zero(), Uint128::MAX, Uint128::one(), Decimal::zero()).unwrap();
+        assert_eq!(segments, vec![MintSegment { burned: Uint128::MAX, ratio: dec(2), minted: Uint128::MAX / Uint128::new(2) }]);
+
+        // Uncapped, the segments past the limit are charged at the highest ratio they reach
+        let linear = MintCurve::Linear { initial: dec(1), slope: dec(1) };
+        let segments = linear
+            .split_mint(Uint128::zero(), Uint128::new(1_000), Uint128::new(1), Decimal::zero())
+            .unwrap();
+        assert_eq!(segments.len(), MAX_MINT_SEGMENTS);
+        assert_eq!(segments.iter().map(|s| s.burned).sum::<Uint128>(), Uint128::new(1_000));
+        let last = segments.last().unwrap();
+        assert_eq!(last.burned, Uint128::new(901));
+        assert_eq!(last.ratio, dec(1_000));
+        assert_eq!(last.minted, Uint128::zero());
+
+        // The drop at a step curve's offset does not count as staying at the cap
+        let dip = MintCurve::Step { offset: Uint128::new(100), base: Decimal::percent(50), increment: Decimal::zero() };
+        let segments = dip.split_mint(Uint128::zero(), Uint128::new(200), THRESHOLD, Decimal::one()).unwrap();
+        assert_eq!(segments[1], MintSegment { burned: Uint128::new(100), ratio: Decimal::percent(50), minted: Uint128::new(200) });
+    }
+
+    #[test]
+    fn test_tokens_for_burn_rounds_down() {
+        assert_eq!(tokens_for_burn(Uint128::new(1_000), dec(3)).unwrap(), Uint128::new(333));
+        assert_eq!(tokens_for_burn(Uint128::new(1_000), Decimal::percent(105)).unwrap(), Uint128::new(952));
+        assert_eq!(tokens_for_burn(Uint128::new(1_000), Decimal::percent(50)).unwrap(), Uint128::new(2_000));
+        assert!(tokens_for_burn(Uint128::new(1_000), Decimal::zero()).is_err());
+        assert!(tokens_for_burn(Uint128::MAX, Decimal::percent(50)).is_err());
+    }
+}
@@ -1,9 +1,125 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError, Timestamp, Uint128};
+use cw_utils::Expiration;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
-    // Add custom errors here
+
+    #[error("Unauthorized: only the admin can {action}")]
+    Unauthorized { action: String },
+
+    #[error("Minting is currently paused")]
+    Paused {},
+
+    #[error("No {denom} sent for minting")]
+    NoFunds { denom: String },
+
+    #[error("Wrong denom sent for minting: expected {expected}, received {received}")]
+    WrongDenom { expected: String, received: String },
+
+    #[error("Send only a single {expected} coin for minting, received {received}")]
+    MultipleCoins { expected: String, received: String },
+
+    #[error("{address} did not answer a CW20 minter query")]
+    NotCw20 { address: String },
+
+    #[error("This contract is not the minter of CW20 {address}")]
+    NotCw20Minter { address: String },
+
+    #[error("Changing the CW20 address requires tokens_minted for the new token")]
+    TokensMintedRequired {},
+
+    #[error("tokens_minted can only be given when the CW20 address changes")]
+    TokensMintedUnchanged {},
+
+
+    #[error("tokens_minted {tokens_minted} exceeds the token's total supply of {total_supply}")]
+    TokensMintedAboveSupply { tokens_minted: Uint128, total_supply: Uint128 },
+
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Burn threshold cannot be zero")]
+    ZeroThreshold {},
+
+    #[error("Invalid mint curve: {reason}")]
+    InvalidCurve { reason: String },
+
+    #[error("Burn of {amount} is below the minimum of {min_burn_amount}")]
+    BurnBelowMinimum { amount: Uint128, min_burn_amount: Uint128 },
+
+    #[error("Burn of {amount} is above the per-transaction maximum of {max_burn_per_tx}")]
+    BurnAboveMaximum { amount: Uint128, max_burn_per_tx: Uint128 },
+
+    #[error("Mint of {minted} tokens exceeds the address's remaining allowance of {remaining}")]
+    AddressMintLimitExceeded { minted: Uint128, remaining: Uint128 },
+
+    #[error("Mint of {minted} tokens exceeds the {remaining} left in the rate limit window, which resets at {resets_at}")]
+    RateLimitExceeded { minted: Uint128, remaining: Uint128, resets_at: Timestamp },
+
+    #[error("Supply cap of {max_total_minted} tokens has been reached")]
+    SupplyCapReached { max_total_minted: Uint128 },
+
+    #[error("Supply cap cannot be raised from {current} to {requested}")]
+    SupplyCapIncrease { current: Uint128, requested: Uint128 },
+
+    #[error("Supply cap cannot be below the {total_tokens_minted} tokens already minted")]
+    InvalidSupplyCap { total_tokens_minted: Uint128 },
+
+    #[error("Rate limit needs a nonzero amount and window")]
+    InvalidRateLimit {},
+
+    #[error("Burning {amount} at mint ratio {ratio} would mint zero tokens")]
+    ZeroMintAmount { amount: Uint128, ratio: Decimal },
+
+    #[error("Cannot migrate from contract {actual}, expected {expected}")]
+    InvalidContractName { expected: String, actual: String },
+
+    #[error("Cannot migrate from version {stored} down to {current}")]
+    CannotMigrateDowngrade { stored: String, current: String },
+
+    #[error("Invalid contract version: {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Desired token amount must be nonzero")]
+    ZeroDesiredTokens {},
+
+    #[error("No single mint can produce {desired} tokens")]
+    UnreachableMintAmount { desired: Uint128 },
+
+    #[error("No pending admin proposal")]
+    NoPendingAdmin {},
+
+    #[error("Only the pending admin {pending_admin} can accept the admin role")]
+    NotPendingAdmin { pending_admin: String },
+
+    #[error("Admin proposal has expired")]
+    AdminProposalExpired {},
+
+    #[error("Admin proposal expiry is already in the past")]
+    InvalidExpiration {},
+
+    #[error("Mint would produce {minted} tokens, less than the minimum of {min_tokens_out}")]
+    SlippageExceeded { minted: Uint128, min_tokens_out: Uint128 },
+
+    #[error("Mint deadline {deadline} has passed")]
+    DeadlineExpired { deadline: Expiration },
+
+    #[error("Cannot recover {denom}, which is or was the burn denom")]
+    CannotRecoverBurnDenom { denom: String },
+
+    #[error("No {denom} held by the contract to recover")]
+    NothingToRecover { denom: String },
+}
+
+/// Queries return `StdError`, so contract errors surface as their message
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        match err {
+            ContractError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
 }
@@ -1,23 +1,93 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Timestamp, Uint128};
+use cw_utils::Expiration;
+use crate::curve::{MintCurve, MintSegment};
+use crate::state::{BurnTarget, MintEvent, MintRecord, RateLimit, LEGACY_BURN_ADDRESS, LEGACY_BURN_DENOM};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub cw20_address: String,
-    pub initial_uluna_burned: Option<Uint128>,
+    /// Defaults to the legacy `uluna` denom
+    #[serde(default = "legacy_burn_denom")]
+    pub burn_denom: String,
+    /// Defaults to the legacy burn address
+    #[serde(default = "legacy_burn_target")]
+    pub burn_target: BurnTarget,
+    #[serde(alias = "initial_uluna_burned")]
+    pub initial_burned: Option<Uint128>,
     pub initial_tokens_minted: Option<Uint128>,
+    pub mint_curve: Option<MintCurve>,
+    /// Most tokens this contract will ever mint; `None` for no cap
+    pub max_total_minted: Option<Uint128>,
+}
+
+fn legacy_burn_denom() -> String {
+    LEGACY_BURN_DENOM.to_string()
+}
+
+fn legacy_burn_target() -> BurnTarget {
+    BurnTarget::Address { address: Addr::unchecked(LEGACY_BURN_ADDRESS) }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    SetCw20Address { address: String },
-    Mint {},
-    UpdateMinter { new_minter: String },
+    /// `tokens_minted` replaces the minted counter; it is required when the token changes
+    /// and refused otherwise. `skip_minter_check` skips verifying that this contract is
+    /// the token's minter, but `tokens_minted` is still checked against the token's supply.
+    SetCw20Address { address: String, tokens_minted: Option<Uint128>, skip_minter_check: Option<bool> },
+    Mint {
+        recipient: Option<String>,
+        /// Fail instead of minting fewer tokens than this
+        min_tokens_out: Option<Uint128>,
+        /// Fail if the transaction lands at or after this block height or time
+        deadline: Option<Expiration>,
+    },
+    /// Mint like `Mint`, then pass the tokens to `contract` with `Cw20ExecuteMsg::Send`,
+    /// which calls its `Receive` hook with `msg`. The hook sees this contract as the sender.
+    MintAndSend {
+        contract: String,
+        msg: Binary,
+        min_tokens_out: Option<Uint128>,
+        deadline: Option<Expiration>,
+    },
+    /// Hand the CW20's minter role to `new_minter`, or remove it with `None`.
+    /// Pauses minting unless the role stays with this contract.
+    UpdateMinter { new_minter: Option<String> },
     SetBurnThreshold { threshold: Uint128 },
-    SetMaxMintRatio { max_ratio: Uint128 },
+    SetMaxMintRatio { max_ratio: Decimal },
+    /// Smallest burn `Mint` accepts; zero disables the minimum
+    SetMinBurnAmount { amount: Uint128 },
+    /// Largest burn a single `Mint` accepts; zero disables the limit
+    SetMaxBurnPerTx { amount: Uint128 },
+    /// Most tokens one burner can mint over its lifetime; zero disables the limit
+    SetMaxMintPerAddress { amount: Uint128 },
+    /// Limit the tokens minted by everyone per time window; `None` removes the limit
+    SetRateLimit { rate_limit: Option<RateLimit> },
+    /// Lower the supply cap, or add one if there is none. It can never be raised.
+    LowerMaxTotalMinted { max_total_minted: Uint128 },
+    /// When enabled, `Mint` burns only what the minted tokens cost and refunds the rest
+    SetRefundRemainder { refund_remainder: bool },
     SetPaused { paused: bool }, // New message to pause/resume minting
+    SetBurnDenom { denom: String },
+    SetBurnTarget { target: BurnTarget },
+    SetMintCurve { curve: MintCurve },
+    ProposeNewAdmin { new_admin: String, expires: Option<Expiration> },
+    AcceptAdmin {},
+    CancelAdminProposal {},
+    RenounceAdmin {},
+    /// Send out funds of a denom other than the burn denom held by this contract.
+    /// `amount` defaults to the whole balance.
+    RecoverFunds { denom: String, amount: Option<Uint128>, recipient: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// Burn denom to use when upgrading a config written before it was configurable
+    pub burn_denom: Option<String>,
+    /// Burn target to use when upgrading a config written before it was configurable
+    pub burn_target: Option<BurnTarget>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -25,6 +95,26 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     GetCw20Address {},
     GetConfig {},
+    GetAdmin {},
+    /// `address` applies that burner's `max_mint_per_address` allowance
+    SimulateMint { amount: Uint128, address: Option<String> },
+    ReverseSimulateMint { desired_tokens: Uint128, address: Option<String> },
+    MintHistory { address: String, start_after: Option<u64>, limit: Option<u32> },
+    AddressTotals { address: String },
+    MintAllowance { address: String },
+    RateLimitStatus {},
+    /// Token info and minter of the CW20, queried from the token itself
+    TokenStatus {},
+    AllMints { start_after: Option<u64>, limit: Option<u32>, order: Option<OrderBy> },
+    TopBurners { limit: Option<u32> },
+    BurnerRank { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderBy {
+    Asc,
+    Desc,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -34,8 +124,150 @@ pub struct Cw20AddressResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub total_uluna_burned: Uint128,
+    pub burn_denom: String,
+    pub burn_target: BurnTarget,
+    pub total_burned: Uint128,
     pub total_tokens_minted: Uint128,
-    pub current_mint_ratio: Uint128,
-    pub max_mint_ratio: Uint128,
+    /// Tokens minted across every CW20 this contract has used; the supply cap applies to it
+    pub lifetime_tokens_minted: Uint128,
+    pub current_mint_ratio: Decimal,
+    pub max_mint_ratio: Decimal,
+    pub min_burn_amount: Uint128,
+    pub refund_remainder: bool,
+    pub max_burn_per_tx: Uint128,
+    pub max_mint_per_address: Uint128,
+    pub rate_limit: Option<RateLimit>,
+    pub max_total_minted: Option<Uint128>,
+    pub burn_threshold: Uint128,
+    pub mint_curve: MintCurve,
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminResponse {
+    pub admin: Option<Addr>,
+    pub pending_admin: Option<Addr>,
+    pub pending_expires: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateMintResponse {
+    /// Part of the amount that would be burned
+    pub burn_amount: Uint128,
+    /// Rounding remainder that would be refunded
+    pub refund_amount: Uint128,
+    pub mint_amount: Uint128,
+    /// Ratio before the mint
+    pub mint_ratio: Decimal,
+    /// Burned amount per minted token across the whole mint
+    pub effective_ratio: Decimal,
+    /// Ratio the next mint would start at
+    pub ratio_after: Decimal,
+    pub max_mint_ratio: Decimal,
+    /// Whether `max_mint_ratio` limits any part of the mint
+    pub max_ratio_applied: bool,
+    pub segments: Vec<MintSegment>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReverseSimulateMintResponse {
+    /// Minimum amount to burn to receive at least the desired tokens
+    pub burn_amount: Uint128,
+    /// Tokens that burn actually mints, which can exceed the target through rounding
+    pub mint_amount: Uint128,
+    /// Ratio before the mint
+    pub mint_ratio: Decimal,
+    pub effective_ratio: Decimal,
+    /// Ratio the next mint would start at
+    pub ratio_after: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintHistoryResponse {
+    pub records: Vec<MintRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AddressTotalsResponse {
+    pub address: Addr,
+    pub total_burned: Uint128,
+    pub total_minted: Uint128,
+    pub mint_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintAllowanceResponse {
+    pub address: Addr,
+    pub total_minted: Uint128,
+    pub max_mint_per_address: Uint128,
+    /// Tokens the address can still mint; `None` when there is no per-address limit
+    pub remaining: Option<Uint128>,
+    pub max_burn_per_tx: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimitStatusResponse {
+    pub rate_limit: Option<RateLimit>,
+    /// Tokens minted in the current window
+    pub used: Uint128,
+    /// Tokens that can still be minted in the current window; `None` without a rate limit
+    pub remaining: Option<Uint128>,
+    pub window_start: Option<Timestamp>,
+    /// When the current window ends and the capacity is restored
+    pub resets_at: Option<Timestamp>,
+}
+
+/// Misconfiguration of the CW20 that keeps `Mint` from working.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStatusIssue {
+    /// This contract is not the CW20's minter
+    NotMinter {},
+    /// The CW20's supply has reached its cap
+    CapReached {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenStatusResponse {
+    pub cw20_address: Addr,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+    /// Current minter of the CW20, if it has one
+    pub minter: Option<String>,
+    /// The CW20's own cap on total supply
+    pub cap: Option<Uint128>,
+    /// Whether this contract is the CW20's minter
+    pub is_minter: bool,
+    pub issues: Vec<TokenStatusIssue>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllMintsResponse {
+    pub mints: Vec<MintEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BurnerInfo {
+    pub address: Addr,
+    pub total_burned: Uint128,
+    pub total_minted: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TopBurnersResponse {
+    pub burners: Vec<BurnerInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BurnerRankResponse {
+    pub address: Addr,
+    /// 1-based position by amount burned, shared on ties; `None` if the address never
+    /// minted or ranks beyond `max_rank`
+    pub rank: Option<u64>,
+    /// Deepest rank the query resolves; its cost grows with the rank, up to this bound
+    pub max_rank: u64,
+    pub total_burned: Uint128,
+    pub total_minted: Uint128,
 }
@@ -1,17 +1,208 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Empty, StdResult, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::curve::{cap_ratio, MintCurve};
+
+/// Burn address hardcoded by the original Luna-only release.
+pub const LEGACY_BURN_ADDRESS: &str = "terra1sk06e3dyexuq4shw77y3dsv480xv42mq73anxu";
+
+/// Burn denom hardcoded by the original Luna-only release.
+pub const LEGACY_BURN_DENOM: &str = "uluna";
+
+/// Where the burn denom received by `Mint` ends up.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnTarget {
+    /// Forward the funds to a (typically unspendable) burn address
+    Address { address: Addr },
+    /// Keep the funds in this contract
+    HoldInContract {},
+    /// Destroy the funds through the bank module with `BankMsg::Burn`
+    ModuleBurn {},
+}
+
+impl fmt::Display for BurnTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BurnTarget::Address { address } => write!(f, "address:{}", address),
+            BurnTarget::HoldInContract {} => write!(f, "hold_in_contract"),
+            BurnTarget::ModuleBurn {} => write!(f, "module_burn"),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
+    pub cw20_address: Addr,
+    pub admin: Option<Addr>, // None once the admin role has been renounced
+    pub burn_denom: String,
+    pub burn_target: BurnTarget,
+    pub total_burned: Uint128,
+    pub total_tokens_minted: Uint128, // Minted on the current CW20
+    pub lifetime_tokens_minted: Uint128, // Minted on every CW20 so far, never reset
+    pub burn_threshold: Uint128,
+    pub max_mint_ratio: Decimal, // Zero means no cap
+    pub min_burn_amount: Uint128, // Zero means no minimum
+    pub refund_remainder: bool, // Refund the part of a burn that mints nothing
+    pub max_burn_per_tx: Uint128, // Zero means no limit
+    pub max_mint_per_address: Uint128, // Lifetime limit per burner, zero means no limit
+    pub rate_limit: Option<RateLimit>,
+    pub max_total_minted: Option<Uint128>, // Can only be lowered once set
+    pub mint_curve: MintCurve,
+    pub paused: bool, // New field to track pause status
+}
+
+impl Config {
+    pub fn is_admin(&self, addr: &Addr) -> bool {
+        self.admin.as_ref() == Some(addr)
+    }
+
+    /// Ratio the next mint is charged at: the curve evaluated at `total_burned`,
+    /// capped by `max_mint_ratio` unless that is zero.
+    pub fn mint_ratio(&self) -> StdResult<Decimal> {
+        let calculated_ratio = self.mint_curve.ratio(self.total_burned, self.burn_threshold)?;
+        Ok(cap_ratio(calculated_ratio, self.max_mint_ratio))
+    }
+
+    /// Tokens that can still be minted under `max_total_minted`, or `None` without a cap.
+    /// Counts everything minted since instantiation, so switching tokens frees no room.
+    pub fn remaining_supply(&self) -> Option<Uint128> {
+        self.max_total_minted.map(|max| max.saturating_sub(self.lifetime_tokens_minted))
+    }
+}
+
+/// Config layout written by the original Luna-only 0.1.0 release, which stored no
+/// cw2 version. Kept so `migrate` can read it back from the same storage key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
     pub cw20_address: Addr,
     pub admin: Addr,
     pub total_uluna_burned: Uint128,
     pub total_tokens_minted: Uint128,
     pub burn_threshold: Uint128,
     pub max_mint_ratio: Uint128,
-    pub paused: bool, // New field to track pause status
+    pub paused: bool,
+}
+
+impl LegacyConfig {
+    pub fn into_config(self, burn_denom: String, burn_target: BurnTarget) -> Config {
+        Config {
+            cw20_address: self.cw20_address,
+            admin: Some(self.admin),
+            burn_denom,
+            burn_target,
+            total_burned: self.total_uluna_burned,
+            total_tokens_minted: self.total_tokens_minted,
+            lifetime_tokens_minted: self.total_tokens_minted,
+            burn_threshold: self.burn_threshold,
+            max_mint_ratio: Decimal::checked_from_ratio(self.max_mint_ratio, 1u128)
+                .unwrap_or(Decimal::MAX),
+            min_burn_amount: Uint128::zero(),
+            refund_remainder: false,
+            max_burn_per_tx: Uint128::zero(),
+            max_mint_per_address: Uint128::zero(),
+            rate_limit: None,
+            max_total_minted: None,
+            mint_curve: MintCurve::default(),
+            paused: self.paused,
+        }
+    }
+}
+
+/// Global issuance limit: at most `max_minted` tokens per window of `window_seconds`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimit {
+    pub max_minted: Uint128,
+    pub window_seconds: u64,
+}
+
+/// Tokens minted in the rate limit window that started at `start`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimitWindow {
+    pub start: Timestamp,
+    pub minted: Uint128,
+}
+
+impl RateLimitWindow {
+    /// The window in effect at `now`: this one, or a fresh one once this has run out.
+    pub fn current(window: Option<Self>, rate_limit: &RateLimit, now: Timestamp) -> Self {
+        match window {
+            Some(window) if now < window.start.plus_seconds(rate_limit.window_seconds) => window,
+            _ => RateLimitWindow { start: now, minted: Uint128::zero() },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdmin {
+    pub address: Addr,
+    pub expires: Expiration,
+}
+
+/// One mint by one burner, stored under `(burner, id)` where `id` counts that
+/// burner's mints from zero. The minted tokens went to `recipient`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintRecord {
+    pub id: u64,
+    pub recipient: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+    pub burned: Uint128,
+    pub minted: Uint128,
+    /// Effective ratio of the mint, burned per minted token
+    pub ratio: Decimal,
+}
+
+/// Entry in the global, append-only mint log, keyed by `id`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintEvent {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub burned: Uint128,
+    pub minted: Uint128,
+    /// Effective ratio of the mint, burned per minted token
+    pub ratio: Decimal,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct AddressTotals {
+    pub burned: Uint128,
+    pub minted: Uint128,
+    pub mint_count: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+pub const MINT_HISTORY: Map<(&Addr, u64), MintRecord> = Map::new("mint_history");
+pub const MINT_EVENTS: Map<u64, MintEvent> = Map::new("mint_events");
+pub const MINT_EVENT_COUNT: Item<u64> = Item::new("mint_event_count");
+pub const RATE_LIMIT_WINDOW: Item<RateLimitWindow> = Item::new("rate_limit_window");
+/// Every denom that has been the burn denom, so funds burned under an old one stay put
+pub const BURN_DENOMS: Map<&str, Empty> = Map::new("burn_denoms");
+
+pub struct AddressTotalsIndexes<'a> {
+    pub burned: MultiIndex<'a, u128, AddressTotals, &'a Addr>,
+}
+
+impl<'a> IndexList<AddressTotals> for AddressTotalsIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AddressTotals>> + '_> {
+        let v: Vec<&dyn Index<AddressTotals>> = vec![&self.burned];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Cumulative totals per burner, indexed by amount burned for the leaderboard.
+pub fn address_totals<'a>() -> IndexedMap<'a, &'a Addr, AddressTotals, AddressTotalsIndexes<'a>> {
+    let indexes = AddressTotalsIndexes {
+        burned: MultiIndex::new(|_, t| t.burned.u128(), "address_totals", "address_totals__burned"),
+    };
+    IndexedMap::new("address_totals", indexes)
+}
@@ -1,25 +1,58 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, ContractResult, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw20::{Cw20QueryMsg, MinterResponse, TokenInfoResponse};
+use cw20_mint_and_burn::contract::{execute, instantiate};
+use cw20_mint_and_burn::msg::{ExecuteMsg, InstantiateMsg};
+use cw20_mint_and_burn::state::BurnTarget;
 
-    #[test]
-    fn test_set_and_mint() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("creator", &[]);
+#[test]
+fn test_set_and_mint() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let info = mock_info("creator", &[]);
 
-        let msg = InstantiateMsg { cw20_address: "cw20_address".to_string() };
-        let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let msg = InstantiateMsg {
+        cw20_address: "cw20_address".to_string(),
+        burn_denom: "uluna".to_string(),
+        burn_target: BurnTarget::Address { address: Addr::unchecked("burn_address") },
+        initial_burned: None,
+        initial_tokens_minted: None,
+        mint_curve: None,
+        max_total_minted: None,
+    };
+    let _res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-        let set_msg = ExecuteMsg::SetCw20Address { address: "new_cw20_address".to_string() };
-        let _res = execute(deps.as_mut(), env.clone(), info.clone(), set_msg).unwrap();
+    // The new token names this contract as its minter
+    let minter = env.contract.address.to_string();
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "new_cw20_address" => {
+            let response = match from_json(msg).unwrap() {
+                Cw20QueryMsg::Minter {} => to_json_binary(&Some(MinterResponse { minter: minter.clone(), cap: None })),
+                Cw20QueryMsg::TokenInfo {} => to_json_binary(&TokenInfoResponse {
+                    name: "Token".to_string(),
+                    symbol: "TKN".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::zero(),
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        },
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
 
-        let mint_msg = ExecuteMsg::Mint {};
-        let info_with_funds = mock_info("sender", &coins(1000, "uluna"));
-        let res = execute(deps.as_mut(), env.clone(), info_with_funds, mint_msg).unwrap();
+    let set_msg = ExecuteMsg::SetCw20Address {
+        address: "new_cw20_address".to_string(),
+        tokens_minted: Some(Uint128::zero()),
+        skip_minter_check: None,
+    };
+    let _res = execute(deps.as_mut(), env.clone(), info.clone(), set_msg).unwrap();
 
-        assert_eq!(res.messages.len(), 2);  // Ensure we have messages for burn and mint
-    }
-}
\ No newline at end of file
+    let mint_msg = ExecuteMsg::Mint { recipient: None, min_tokens_out: None, deadline: None };
+    let info_with_funds = mock_info("sender", &coins(1000, "uluna"));
+    let res = execute(deps.as_mut(), env.clone(), info_with_funds, mint_msg).unwrap();
+
+    assert_eq!(res.messages.len(), 2);  // Ensure we have messages for burn and mint
+}